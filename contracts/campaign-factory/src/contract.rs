@@ -1,29 +1,39 @@
 use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg};
 use crate::state::{
-    Config, ConfigResponse, CreateCampaign, FactoryCampaign, ADDR_CAMPAIGNS, CONFIG,
-    NUMBER_OF_CAMPAIGNS,
+    campaigns, CampaignStatus, CampaignsResponse, Config, ConfigResponse, CreateCampaign,
+    FactoryCampaign, FactoryCampaignResponse, CAMPAIGN_IDS, CONFIG, NUMBER_OF_CAMPAIGNS,
+    PENDING_REWARD, VERIFIED_COLLECTIONS, VERIFIED_REWARD_TOKENS,
 };
-use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::CAMPAIGNS,
-};
-// use campaign::msg::ExecuteMsg as CampaignExecuteMsg;
+use campaign::msg::Cw20HookMsg as CampaignCw20HookMsg;
+use campaign::msg::ExecuteMsg as CampaignExecuteMsg;
 use campaign::msg::InstantiateMsg as CampaignInstantiateMsg;
 use campaign::msg::QueryMsg as CampaignQueryMsg;
-use campaign::state::CampaignInfoResult;
+use campaign::state::{AssetToken, CampaignInfoResult, TokenInfo};
+use campaign::utils::MAX_PROTOCOL_FEE_BPS;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, QuerierWrapper,
-    QueryRequest, Reply, ReplyOn, Response, StdResult, SubMsg, WasmMsg, WasmQuery,
+    from_binary, instantiate2_address, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, QuerierWrapper, QueryRequest, Reply, ReplyOn,
+    Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::set_contract_version;
-use cw_utils::parse_reply_instantiate_data;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, nonpayable, parse_reply_instantiate_data};
+use sha2::{Digest, Sha256};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:campaign-factory";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 30;
+// max campaigns scanned by a page of campaigns filtered by status
+const MAX_SCAN: usize = 300;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -33,18 +43,36 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let treasury = match msg.treasury {
+        Some(treasury) => deps.api.addr_validate(&treasury)?,
+        None => info.sender.clone(),
+    };
+    let fee_exempt = msg
+        .fee_exempt
+        .unwrap_or_default()
+        .iter()
+        .map(|addr| deps.api.addr_validate(addr))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let protocol_fee_bps = msg.protocol_fee_bps.unwrap_or(0);
+    if protocol_fee_bps > MAX_PROTOCOL_FEE_BPS {
+        return Err(ContractError::InvalidProtocolFee {});
+    }
+
     let config = Config {
         owner: info.sender,
         campaign_code_id: msg.campaign_code_id,
         allow_create_for_all: msg.allow_create_for_all,
+        creation_fee: validate_creation_fee(deps.as_ref(), msg.creation_fee)?,
+        treasury,
+        fee_exempt,
+        protocol_fee_bps,
+        allow_unverified: msg.allow_unverified.unwrap_or(true),
     };
 
     // init NUMBER_OF_CAMPAIGNS to 0
     NUMBER_OF_CAMPAIGNS.save(deps.storage, &0u64)?;
 
-    // init ADDR_CAMPAIGNS to vec![]
-    ADDR_CAMPAIGNS.save(deps.storage, &vec![])?;
-
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new())
@@ -62,6 +90,11 @@ pub fn execute(
             owner,
             campaign_code_id,
             allow_create_for_all,
+            creation_fee,
+            treasury,
+            fee_exempt,
+            protocol_fee_bps,
+            allow_unverified,
         } => execute_update_config(
             deps,
             env,
@@ -69,14 +102,75 @@ pub fn execute(
             owner,
             campaign_code_id,
             allow_create_for_all,
+            creation_fee,
+            treasury,
+            fee_exempt,
+            protocol_fee_bps,
+            allow_unverified,
         ),
-        ExecuteMsg::CreateCampaign { create_campaign } => {
-            execute_create_campaign(deps, env, info, create_campaign)
+        ExecuteMsg::CreateCampaign {
+            create_campaign,
+            salt,
+        } => execute_create_campaign(deps, env, info, create_campaign, salt),
+        ExecuteMsg::CancelCampaign { campaign_id } => {
+            execute_cancel_campaign(deps, env, info, campaign_id)
+        }
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::UpdateVerifiedCollections { add, remove } => {
+            execute_update_verified_collections(deps, env, info, add, remove)
+        }
+        ExecuteMsg::UpdateVerifiedRewardTokens { add, remove } => {
+            execute_update_verified_reward_tokens(deps, env, info, add, remove)
+        }
+    }
+}
+
+// Creator sends reward token to create a funded campaign
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&msg.msg)? {
+        Cw20HookMsg::CreateCampaign {
+            create_campaign,
+            salt,
+        } => {
+            // sent token must be the reward token of campaign
+            if create_campaign.reward_token_info.info
+                != (TokenInfo::Token {
+                    contract_addr: info.sender.to_string(),
+                })
+            {
+                return Err(ContractError::InvalidRewardToken {});
+            }
+
+            // cw20 sender is only trusted from a verified token, another token can name any creator
+            if !VERIFIED_REWARD_TOKENS.has(deps.storage, info.sender.to_string()) {
+                return Err(ContractError::Unverified {});
+            }
+            if msg.amount.is_zero() {
+                return Err(ContractError::InvalidFunds {});
+            }
+
+            let config: Config = CONFIG.load(deps.storage)?;
+            let creator = deps.api.addr_validate(&msg.sender)?;
+            let (fee_msg, reward) =
+                collect_cw20_creation_fee(&config, &creator, &info.sender, msg.amount)?;
+
+            // reward is forwarded to campaign once it is instantiated
+            if !reward.is_zero() {
+                PENDING_REWARD.save(deps.storage, &reward)?;
+            }
+
+            instantiate_campaign(deps, env, &config, &creator, create_campaign, salt, fee_msg)
         }
     }
 }
 
 // Only owner can execute it
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
     _env: Env,
@@ -84,6 +178,11 @@ pub fn execute_update_config(
     owner: Option<Addr>,
     campaign_code_id: Option<u64>,
     allow_create_for_all: Option<bool>,
+    creation_fee: Option<AssetToken>,
+    treasury: Option<Addr>,
+    fee_exempt: Option<Vec<Addr>>,
+    protocol_fee_bps: Option<u64>,
+    allow_unverified: Option<bool>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
@@ -110,6 +209,32 @@ pub fn execute_update_config(
         config.allow_create_for_all
     };
 
+    if let Some(new_creation_fee) = creation_fee {
+        config.creation_fee = validate_creation_fee(deps.as_ref(), Some(new_creation_fee))?;
+    }
+
+    if let Some(new_treasury) = treasury {
+        config.treasury = deps.api.addr_validate(new_treasury.as_str())?;
+    }
+
+    if let Some(new_fee_exempt) = fee_exempt {
+        config.fee_exempt = new_fee_exempt
+            .iter()
+            .map(|addr| deps.api.addr_validate(addr.as_str()))
+            .collect::<StdResult<Vec<_>>>()?;
+    }
+
+    if let Some(new_protocol_fee_bps) = protocol_fee_bps {
+        if new_protocol_fee_bps > MAX_PROTOCOL_FEE_BPS {
+            return Err(ContractError::InvalidProtocolFee {});
+        }
+        config.protocol_fee_bps = new_protocol_fee_bps;
+    }
+
+    if let Some(new_allow_unverified) = allow_unverified {
+        config.allow_unverified = new_allow_unverified;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
@@ -119,7 +244,250 @@ pub fn execute_update_config(
         .add_attribute(
             "allow_create_for_all",
             config.allow_create_for_all.to_string(),
-        ))
+        )
+        .add_attribute(
+            "creation_fee",
+            config
+                .creation_fee
+                .map_or("none".to_string(), |fee| fee.to_string()),
+        )
+        .add_attribute("treasury", config.treasury)
+        .add_attribute("protocol_fee_bps", config.protocol_fee_bps.to_string())
+        .add_attribute("allow_unverified", config.allow_unverified.to_string()))
+}
+
+// Only owner can execute it
+pub fn execute_update_verified_collections(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for collection in add {
+        let collection = deps.api.addr_validate(&collection)?;
+        VERIFIED_COLLECTIONS.save(deps.storage, &collection, &Empty {})?;
+    }
+    for collection in remove {
+        let collection = deps.api.addr_validate(&collection)?;
+        VERIFIED_COLLECTIONS.remove(deps.storage, &collection);
+    }
+
+    Ok(Response::new().add_attribute("method", "update_verified_collections"))
+}
+
+// Only owner can execute it
+pub fn execute_update_verified_reward_tokens(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<TokenInfo>,
+    remove: Vec<TokenInfo>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for reward_token in add {
+        let reward_token = validate_reward_token(deps.as_ref(), reward_token)?;
+        VERIFIED_REWARD_TOKENS.save(deps.storage, reward_token.to_string(), &reward_token)?;
+    }
+    for reward_token in remove {
+        let reward_token = validate_reward_token(deps.as_ref(), reward_token)?;
+        VERIFIED_REWARD_TOKENS.remove(deps.storage, reward_token.to_string());
+    }
+
+    Ok(Response::new().add_attribute("method", "update_verified_reward_tokens"))
+}
+
+// cw20 reward token is keyed by its validated address
+fn validate_reward_token(deps: Deps, reward_token: TokenInfo) -> StdResult<TokenInfo> {
+    match reward_token {
+        TokenInfo::Token { contract_addr } => Ok(TokenInfo::Token {
+            contract_addr: deps.api.addr_validate(&contract_addr)?.to_string(),
+        }),
+        native_token => Ok(native_token),
+    }
+}
+
+// campaign is verified if both its collection and reward token are verified
+fn is_verified(storage: &dyn Storage, collection: &Addr, reward_token: &TokenInfo) -> bool {
+    VERIFIED_COLLECTIONS.has(storage, collection)
+        && VERIFIED_REWARD_TOKENS.has(storage, reward_token.to_string())
+}
+
+// verified of campaign follows the registries, it is not saved at creation
+fn campaign_response(storage: &dyn Storage, campaign: FactoryCampaign) -> FactoryCampaignResponse {
+    FactoryCampaignResponse {
+        verified: is_verified(
+            storage,
+            &campaign.allowed_collection,
+            &campaign.reward_token,
+        ),
+        owner: campaign.owner,
+        campaign_addr: campaign.campaign_addr,
+        reward_token: campaign.reward_token,
+        allowed_collection: campaign.allowed_collection,
+        campaign_id: campaign.campaign_id,
+        start_time: campaign.start_time,
+        end_time: campaign.end_time,
+        cancelled: campaign.cancelled,
+    }
+}
+
+// creation fee with zero amount is no fee
+fn validate_creation_fee(
+    deps: Deps,
+    creation_fee: Option<AssetToken>,
+) -> Result<Option<AssetToken>, ContractError> {
+    match creation_fee {
+        Some(fee) if !fee.amount.is_zero() => {
+            if let TokenInfo::Token { contract_addr } = &fee.info {
+                deps.api.addr_validate(contract_addr)?;
+            }
+            Ok(Some(fee))
+        }
+        _ => Ok(None),
+    }
+}
+
+// message sending creation fee paid by creator to treasury
+fn collect_creation_fee(
+    config: &Config,
+    info: &MessageInfo,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let creation_fee = match &config.creation_fee {
+        Some(fee) if !config.fee_exempt.contains(&info.sender) => fee,
+        _ => {
+            // funds would be stuck in factory
+            nonpayable(info)?;
+            return Ok(None);
+        }
+    };
+
+    let fee_msg = match &creation_fee.info {
+        TokenInfo::NativeToken { denom } => {
+            if must_pay(info, denom)? != creation_fee.amount {
+                return Err(ContractError::InvalidCreationFee {});
+            }
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: config.treasury.to_string(),
+                amount: vec![Coin {
+                    denom: denom.clone(),
+                    amount: creation_fee.amount,
+                }],
+            })
+        }
+        TokenInfo::Token { contract_addr } => {
+            nonpayable(info)?;
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: config.treasury.to_string(),
+                    amount: creation_fee.amount,
+                })?,
+                funds: vec![],
+            })
+        }
+    };
+
+    Ok(Some(fee_msg))
+}
+
+// native reward sent with create campaign, funds left in returned info pay creation fee.
+// Creation fee in reward denom is kept out of reward.
+fn split_native_reward(
+    config: &Config,
+    create_campaign: &CreateCampaign,
+    info: &MessageInfo,
+) -> (MessageInfo, Uint128) {
+    let denom = match &create_campaign.reward_token_info.info {
+        TokenInfo::NativeToken { denom } => denom,
+        TokenInfo::Token { .. } => return (info.clone(), Uint128::zero()),
+    };
+
+    let sent: Uint128 = info
+        .funds
+        .iter()
+        .filter(|coin| &coin.denom == denom)
+        .map(|coin| coin.amount)
+        .sum();
+    let fee = match &config.creation_fee {
+        Some(AssetToken {
+            info: TokenInfo::NativeToken { denom: fee_denom },
+            amount,
+        }) if fee_denom == denom && !config.fee_exempt.contains(&info.sender) => *amount,
+        _ => Uint128::zero(),
+    };
+    let reward = sent.saturating_sub(fee);
+
+    let funds = info
+        .funds
+        .iter()
+        .filter(|coin| &coin.denom != denom)
+        .cloned()
+        .chain((sent > reward).then(|| Coin {
+            denom: denom.clone(),
+            amount: sent - reward,
+        }))
+        .collect();
+    let fee_info = MessageInfo {
+        sender: info.sender.clone(),
+        funds,
+    };
+
+    (fee_info, reward)
+}
+
+// creation fee of campaign funded by cw20 send, returns reward left for campaign.
+// Fee in sent token is taken from sent amount, fee in other cw20 token is transferred from
+// creator. Native fee can not be paid, no native funds come with cw20 send.
+fn collect_cw20_creation_fee(
+    config: &Config,
+    creator: &Addr,
+    token: &Addr,
+    amount: Uint128,
+) -> Result<(Option<CosmosMsg>, Uint128), ContractError> {
+    let creation_fee = match &config.creation_fee {
+        Some(fee) if !config.fee_exempt.contains(creator) => fee,
+        _ => return Ok((None, amount)),
+    };
+
+    match &creation_fee.info {
+        TokenInfo::Token { contract_addr } if contract_addr == token.as_str() => {
+            let reward = amount
+                .checked_sub(creation_fee.amount)
+                .map_err(|_| ContractError::InvalidCreationFee {})?;
+            let fee_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: config.treasury.to_string(),
+                    amount: creation_fee.amount,
+                })?,
+                funds: vec![],
+            });
+            Ok((Some(fee_msg), reward))
+        }
+        TokenInfo::Token { .. } => {
+            let creator = MessageInfo {
+                sender: creator.clone(),
+                funds: vec![],
+            };
+            Ok((collect_creation_fee(config, &creator)?, amount))
+        }
+        TokenInfo::NativeToken { .. } => Err(ContractError::InvalidCreationFee {}),
+    }
 }
 
 // Anyone can execute it to create a new pool
@@ -129,15 +497,105 @@ pub fn execute_create_campaign(
     env: Env,
     info: MessageInfo,
     create_campaign: CreateCampaign,
+    salt: Option<Binary>,
 ) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
+    // native reward is forwarded to campaign once it is instantiated
+    let (fee_info, reward) = split_native_reward(&config, &create_campaign, &info);
+    if !reward.is_zero() {
+        PENDING_REWARD.save(deps.storage, &reward)?;
+    }
+
+    // creation fee is sent to treasury, unless creator is exempt
+    let fee_msg = collect_creation_fee(&config, &fee_info)?;
+
+    instantiate_campaign(
+        deps,
+        env,
+        &config,
+        &info.sender,
+        create_campaign,
+        salt,
+        fee_msg,
+    )
+}
+
+fn instantiate_campaign(
+    deps: DepsMut,
+    env: Env,
+    config: &Config,
+    creator: &Addr,
+    create_campaign: CreateCampaign,
+    salt: Option<Binary>,
+    fee_msg: Option<CosmosMsg>,
+) -> Result<Response, ContractError> {
     // only owner can create || config.allow_create_for_all == true
-    if !config.allow_create_for_all && config.owner != info.sender {
+    if !config.allow_create_for_all && config.owner != creator {
         return Err(ContractError::Unauthorized {});
     }
 
+    // unverified assets are rejected, unless allowed by config
+    let collection = deps
+        .api
+        .addr_validate(&create_campaign.allowed_collection)?;
+    if !config.allow_unverified
+        && !is_verified(
+            deps.storage,
+            &collection,
+            &create_campaign.reward_token_info.info,
+        )
+    {
+        return Err(ContractError::Unverified {});
+    }
+
+    let admin = Some(env.contract.address.to_string());
+    let label = "pair".to_string();
+    let msg = to_binary(&CampaignInstantiateMsg {
+        owner: create_campaign.owner.clone(),
+        campaign_name: create_campaign.campaign_name.clone(),
+        campaign_image: create_campaign.campaign_image.clone(),
+        campaign_description: create_campaign.campaign_description.clone(),
+        limit_per_staker: create_campaign.limit_per_staker,
+        reward_token_info: create_campaign.reward_token_info.clone(),
+        allowed_collection: create_campaign.allowed_collection.clone(),
+        lockup_term: create_campaign.lockup_term.clone(),
+        start_time: create_campaign.start_time,
+        end_time: create_campaign.end_time,
+        distribution_mode: create_campaign.distribution_mode,
+        loyalty_steps: create_campaign.loyalty_steps,
+        emission_schedule: create_campaign.emission_schedule,
+        rollover_unallocated: create_campaign.rollover_unallocated,
+        receipt_code_id: create_campaign.receipt_code_id,
+        reward_mode: create_campaign.reward_mode,
+        soft_staking: create_campaign.soft_staking,
+        voting_power_by_lockup: create_campaign.voting_power_by_lockup,
+        protocol_fee_bps: Some(config.protocol_fee_bps),
+        protocol_fee_recipient: Some(config.treasury.to_string()),
+    })?;
+
+    // salted campaign address is known before it is instantiated, salt is scoped by the
+    // authenticated creator: sender of the message or of a verified cw20 token
+    let instantiate_msg = match salt {
+        Some(salt) => WasmMsg::Instantiate2 {
+            admin,
+            code_id: config.campaign_code_id,
+            label,
+            msg,
+            funds: vec![],
+            salt: campaign_salt(deps.as_ref(), creator, &salt)?,
+        },
+        None => WasmMsg::Instantiate {
+            admin,
+            code_id: config.campaign_code_id,
+            msg,
+            funds: vec![],
+            label,
+        },
+    };
+
     Ok(Response::new()
+        .add_messages(fee_msg)
         .add_attributes(vec![
             ("method", "create_campaign"),
             ("campaign_owner", create_campaign.owner.as_str()),
@@ -172,32 +630,59 @@ pub fn execute_create_campaign(
         .add_submessage(SubMsg {
             id: 1,
             gas_limit: None,
-            msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
-                code_id: config.campaign_code_id,
-                funds: vec![],
-                admin: Some(env.contract.address.to_string()),
-                label: "pair".to_string(),
-                msg: to_binary(&CampaignInstantiateMsg {
-                    owner: create_campaign.owner,
-                    campaign_name: create_campaign.campaign_name,
-                    campaign_image: create_campaign.campaign_image,
-                    campaign_description: create_campaign.campaign_description,
-                    limit_per_staker: create_campaign.limit_per_staker,
-                    reward_token_info: create_campaign.reward_token_info,
-                    allowed_collection: create_campaign.allowed_collection,
-                    lockup_term: create_campaign.lockup_term,
-                    start_time: create_campaign.start_time,
-                    end_time: create_campaign.end_time,
-                })?,
-            }),
+            msg: CosmosMsg::Wasm(instantiate_msg),
             reply_on: ReplyOn::Success,
         }))
 }
 
+// Only factory owner or campaign owner can execute it
+pub fn execute_cancel_campaign(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    campaign_id: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut campaign = campaigns().load(deps.storage, campaign_id)?;
+
+    // permission check
+    if info.sender != config.owner && info.sender != campaign.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if campaign.cancelled {
+        return Err(ContractError::CampaignCancelled {});
+    }
+
+    // stakers may have locked nfts for reward of started campaign
+    let current_time = env.block.time.seconds();
+    if campaign.start_time <= current_time {
+        return Err(ContractError::CampaignStarted {});
+    }
+
+    // campaign ends now, cached times follow the campaign
+    campaign.cancelled = true;
+    campaign.start_time = current_time;
+    campaign.end_time = current_time;
+    campaigns().save(deps.storage, campaign_id, &campaign)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: campaign.campaign_addr.to_string(),
+            msg: to_binary(&CampaignExecuteMsg::Cancel {})?,
+            funds: vec![],
+        })
+        .add_attributes([
+            ("method", "cancel_campaign"),
+            ("campaign_id", &campaign_id.to_string()),
+            ("campaign_addr", campaign.campaign_addr.as_ref()),
+        ]))
+}
+
 /// This just stores the result for future query
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
-    let reply = parse_reply_instantiate_data(msg).unwrap();
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let reply = parse_reply_instantiate_data(msg)?;
 
     let campaign_contract = &reply.contract_address;
     let campaign_info: CampaignInfoResult =
@@ -205,25 +690,59 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
 
     let campaign_key = NUMBER_OF_CAMPAIGNS.load(deps.storage)? + 1;
 
-    CAMPAIGNS.save(
+    let campaign_addr = deps.api.addr_validate(campaign_contract)?;
+
+    campaigns().save(
         deps.storage,
         campaign_key,
         &FactoryCampaign {
             owner: campaign_info.owner.clone(),
-            campaign_addr: deps.api.addr_validate(campaign_contract)?,
-            reward_token: campaign_info.reward_token_info.info,
+            campaign_addr: campaign_addr.clone(),
+            reward_token: campaign_info.reward_token_info.info.clone(),
             allowed_collection: campaign_info.allowed_collection,
+            campaign_id: campaign_key,
+            start_time: campaign_info.start_time,
+            end_time: campaign_info.end_time,
+            cancelled: false,
         },
     )?;
 
     // increase campaign count
     NUMBER_OF_CAMPAIGNS.save(deps.storage, &(campaign_key))?;
 
-    let mut addr_campaigns = ADDR_CAMPAIGNS.load(deps.storage)?;
-    addr_campaigns.push(campaign_contract.clone());
-    ADDR_CAMPAIGNS.save(deps.storage, &addr_campaigns)?;
+    // map campaign address to its id
+    CAMPAIGN_IDS.save(deps.storage, &campaign_addr, &campaign_key)?;
+
+    let mut res = Response::new();
 
-    Ok(Response::new().add_attributes([
+    // deposit reward sent with create campaign, in the same transaction
+    if let Some(reward_amount) = PENDING_REWARD.may_load(deps.storage)? {
+        PENDING_REWARD.remove(deps.storage);
+        let deposit_msg = match &campaign_info.reward_token_info.info {
+            TokenInfo::Token { contract_addr } => WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: campaign_contract.clone(),
+                    amount: reward_amount,
+                    msg: to_binary(&CampaignCw20HookMsg::AddRewardToken {})?,
+                })?,
+                funds: vec![],
+            },
+            TokenInfo::NativeToken { denom } => WasmMsg::Execute {
+                contract_addr: campaign_contract.clone(),
+                msg: to_binary(&CampaignExecuteMsg::AddRewardToken {
+                    amount: reward_amount,
+                })?,
+                funds: vec![Coin {
+                    denom: denom.clone(),
+                    amount: reward_amount,
+                }],
+            },
+        };
+        res = res.add_message(deposit_msg);
+    }
+
+    Ok(res.add_attributes([
         ("action", "reply_on_create_campaign_success"),
         ("campaign_key", campaign_key.to_string().as_str()),
         ("campaign_contract_addr", campaign_contract),
@@ -232,14 +751,53 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::Campaign { campaign_id } => to_binary(&query_campaign_info(deps, campaign_id)?),
-        QueryMsg::Campaigns { start_after, limit } => {
-            to_binary(&query_campaigns(deps, start_after, limit)?)
+        QueryMsg::Campaigns {
+            status,
+            start_after,
+            limit,
+        } => to_binary(&query_campaigns(deps, env, status, start_after, limit)?),
+        QueryMsg::CampaignAddrs { start_after, limit } => {
+            to_binary(&query_addr_campaigns(deps, start_after, limit)?)
+        }
+        QueryMsg::CampaignByAddr { addr } => to_binary(&query_campaign_by_addr(deps, addr)?),
+        QueryMsg::CampaignsByOwner {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_campaigns_by_owner(deps, owner, start_after, limit)?),
+        QueryMsg::CampaignsByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&query_campaigns_by_collection(
+            deps,
+            collection,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::CampaignsByRewardToken {
+            reward_token,
+            start_after,
+            limit,
+        } => to_binary(&query_campaigns_by_reward_token(
+            deps,
+            reward_token,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::VerifiedCollections { start_after, limit } => {
+            to_binary(&query_verified_collections(deps, start_after, limit)?)
+        }
+        QueryMsg::VerifiedRewardTokens { start_after, limit } => {
+            to_binary(&query_verified_reward_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::PredictCampaignAddress { creator, salt } => {
+            to_binary(&query_predict_campaign_address(deps, env, creator, salt)?)
         }
-        QueryMsg::CampaignAddrs {} => to_binary(&query_addr_campaigns(deps)?),
     }
 }
 
@@ -248,36 +806,209 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let resp = ConfigResponse {
         owner: state.owner.to_string(),
         campaign_code_id: state.campaign_code_id,
+        creation_fee: state.creation_fee,
+        treasury: state.treasury.to_string(),
+        fee_exempt: state.fee_exempt.into_iter().map(String::from).collect(),
+        protocol_fee_bps: state.protocol_fee_bps,
+        allow_unverified: state.allow_unverified,
     };
 
     Ok(resp)
 }
 
-pub fn query_campaign_info(deps: Deps, campaign_id: u64) -> StdResult<FactoryCampaign> {
-    let campaign_info = CAMPAIGNS.load(deps.storage, campaign_id)?;
-    Ok(campaign_info)
+pub fn query_campaign_info(deps: Deps, campaign_id: u64) -> StdResult<FactoryCampaignResponse> {
+    let campaign_info = campaigns().load(deps.storage, campaign_id)?;
+    Ok(campaign_response(deps.storage, campaign_info))
 }
 
 pub fn query_campaigns(
     deps: Deps,
+    env: Env,
+    status: Option<CampaignStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<CampaignsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let current_time = env.block.time.seconds();
+
+    let mut res = CampaignsResponse {
+        campaigns: vec![],
+        next_start_after: None,
+    };
+    let mut last_scanned = None;
+    let range = campaigns().range(deps.storage, start, None, Order::Ascending);
+    for (scanned, item) in range.enumerate() {
+        let (campaign_id, campaign) = item?;
+
+        // page is full or scanned enough, next page continues after last scanned campaign
+        if res.campaigns.len() == limit || scanned == MAX_SCAN {
+            res.next_start_after = last_scanned;
+            break;
+        }
+        last_scanned = Some(campaign_id);
+
+        if status
+            .as_ref()
+            .map_or(true, |status| campaign.status(current_time) == *status)
+        {
+            res.campaigns
+                .push(campaign_response(deps.storage, campaign));
+        }
+    }
+
+    Ok(res)
+}
+
+pub fn query_addr_campaigns(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    CAMPAIGN_IDS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|addr| addr.map(String::from))
+        .collect()
+}
+
+pub fn query_campaign_by_addr(deps: Deps, addr: String) -> StdResult<FactoryCampaignResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let campaign_id = CAMPAIGN_IDS.load(deps.storage, &addr)?;
+    let campaign_info = campaigns().load(deps.storage, campaign_id)?;
+    Ok(campaign_response(deps.storage, campaign_info))
+}
+
+pub fn query_campaigns_by_owner(
+    deps: Deps,
+    owner: String,
     start_after: Option<u64>,
     limit: Option<u32>,
-) -> StdResult<Vec<FactoryCampaign>> {
-    let start_after = start_after.unwrap_or(0);
-    let limit = limit.unwrap_or(30) as usize;
-    let campaign_count = NUMBER_OF_CAMPAIGNS.load(deps.storage)?;
+) -> StdResult<Vec<FactoryCampaignResponse>> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
 
-    let campaigns = (start_after..campaign_count)
-        .map(|pool_id| CAMPAIGNS.load(deps.storage, pool_id + 1))
+    campaigns()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
-        .collect::<StdResult<Vec<_>>>()?;
+        .map(|item| item.map(|(_, campaign)| campaign_response(deps.storage, campaign)))
+        .collect()
+}
 
-    Ok(campaigns)
+pub fn query_campaigns_by_collection(
+    deps: Deps,
+    collection: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FactoryCampaignResponse>> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    campaigns()
+        .idx
+        .collection
+        .prefix(collection)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, campaign)| campaign_response(deps.storage, campaign)))
+        .collect()
 }
 
-pub fn query_addr_campaigns(deps: Deps) -> StdResult<Vec<String>> {
-    let addr_campaigns = ADDR_CAMPAIGNS.load(deps.storage)?;
-    Ok(addr_campaigns)
+pub fn query_campaigns_by_reward_token(
+    deps: Deps,
+    reward_token: TokenInfo,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FactoryCampaignResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    campaigns()
+        .idx
+        .reward_token
+        .prefix(reward_token.to_string())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, campaign)| campaign_response(deps.storage, campaign)))
+        .collect()
+}
+
+pub fn query_verified_collections(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    VERIFIED_COLLECTIONS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|addr| addr.map(String::from))
+        .collect()
+}
+
+pub fn query_verified_reward_tokens(
+    deps: Deps,
+    start_after: Option<TokenInfo>,
+    limit: Option<u32>,
+) -> StdResult<Vec<TokenInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|token| Bound::exclusive(token.to_string()));
+
+    VERIFIED_REWARD_TOKENS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, token)| token))
+        .collect()
+}
+
+pub fn query_predict_campaign_address(
+    deps: Deps,
+    env: Env,
+    creator: String,
+    salt: Binary,
+) -> StdResult<Addr> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let creator = deps.api.addr_validate(&creator)?;
+
+    let checksum = deps
+        .querier
+        .query_wasm_code_info(config.campaign_code_id)?
+        .checksum;
+    let factory = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let salt = campaign_salt(deps, &creator, &salt)?;
+
+    let campaign_addr = instantiate2_address(&checksum, &factory, &salt)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    deps.api.addr_humanize(&campaign_addr)
+}
+
+// salt is namespaced by creator, so it can not be taken by another creator
+fn campaign_salt(deps: Deps, creator: &Addr, salt: &Binary) -> StdResult<Binary> {
+    let creator = deps.api.addr_canonicalize(creator.as_str())?;
+
+    let mut hasher = Sha256::new();
+    hasher.update([creator.len() as u8]);
+    hasher.update(creator.as_slice());
+    hasher.update(salt.as_slice());
+
+    Ok(Binary::from(hasher.finalize().to_vec()))
 }
 
 fn query_pair_info_from_pair(
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,33 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
     #[error("## Unauthorized ##")]
     Unauthorized {},
+
+    #[error("## Invalid protocol fee ##")]
+    InvalidProtocolFee {},
+
+    #[error("## Sent token is not the reward token of campaign ##")]
+    InvalidRewardToken {},
+
+    #[error("## Invalid funds ##")]
+    InvalidFunds {},
+
+    #[error("## Invalid creation fee ##")]
+    InvalidCreationFee {},
+
+    #[error("## Collection or reward token is not verified ##")]
+    Unverified {},
+
+    #[error("## Campaign is already cancelled ##")]
+    CampaignCancelled {},
+
+    #[error("## Campaign has already started ##")]
+    CampaignStarted {},
 }
@@ -1,24 +1,74 @@
-use crate::state::{ConfigResponse, CreateCampaign, FactoryCampaign};
+use crate::state::{
+    CampaignStatus, CampaignsResponse, ConfigResponse, CreateCampaign, FactoryCampaignResponse,
+};
+use campaign::state::{AssetToken, TokenInfo};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary};
+use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
 pub struct InstantiateMsg {
     /// Campaign code ID
     pub campaign_code_id: u64,
     pub allow_create_for_all: bool,
+    /// Fee to create campaign, default no fee
+    pub creation_fee: Option<AssetToken>,
+    /// Receiver of creation fee, default sender
+    pub treasury: Option<String>,
+    pub fee_exempt: Option<Vec<String>>,
+    /// Fee on claimed reward in basis points, default 0
+    pub protocol_fee_bps: Option<u64>,
+    /// Create campaigns of unverified collection or reward token, default true
+    pub allow_unverified: Option<bool>,
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// UpdateConfig update relevant code IDs
     UpdateConfig {
         owner: Option<Addr>,
         campaign_code_id: Option<u64>,
         allow_create_for_all: Option<bool>,
+        /// zero amount removes creation fee
+        creation_fee: Option<AssetToken>,
+        treasury: Option<Addr>,
+        fee_exempt: Option<Vec<Addr>>,
+        /// applies to campaigns created after update
+        protocol_fee_bps: Option<u64>,
+        allow_unverified: Option<bool>,
     },
     /// CreateCampaign instantiates pair contract
-    CreateCampaign { create_campaign: CreateCampaign },
+    /// salt instantiates it at the address given by PredictCampaignAddress
+    /// native reward sent with it, less creation fee in the same denom, is its reward deposit
+    CreateCampaign {
+        create_campaign: CreateCampaign,
+        salt: Option<Binary>,
+    },
+    /// CancelCampaign cancels campaign which has not started, by factory owner or campaign owner
+    CancelCampaign { campaign_id: u64 },
+    /// UpdateVerifiedCollections adds and removes verified collections, by owner
+    UpdateVerifiedCollections {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// UpdateVerifiedRewardTokens adds and removes verified reward tokens, by owner
+    UpdateVerifiedRewardTokens {
+        add: Vec<TokenInfo>,
+        remove: Vec<TokenInfo>,
+    },
+    /// Receive creates campaign funded with the sent reward token, which must be verified
+    Receive(Cw20ReceiveMsg),
+}
+
+#[cw_serde]
+#[allow(clippy::large_enum_variant)]
+pub enum Cw20HookMsg {
+    /// CreateCampaign instantiates campaign, sent amount is its reward deposit
+    CreateCampaign {
+        create_campaign: CreateCampaign,
+        salt: Option<Binary>,
+    },
 }
 
 #[cw_serde]
@@ -27,15 +77,64 @@ pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
 
-    #[returns(FactoryCampaign)]
+    #[returns(FactoryCampaignResponse)]
     Campaign { campaign_id: u64 },
 
-    #[returns(Vec<FactoryCampaign>)]
+    /// status filters campaigns against current block time, a page scans at most
+    /// 300 campaigns and continues from next_start_after
+    #[returns(CampaignsResponse)]
     Campaigns {
+        status: Option<CampaignStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// start_after is a campaign address
+    #[returns(Vec<String>)]
+    CampaignAddrs {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(FactoryCampaignResponse)]
+    CampaignByAddr { addr: String },
+
+    /// start_after is a campaign id
+    #[returns(Vec<FactoryCampaignResponse>)]
+    CampaignsByOwner {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<FactoryCampaignResponse>)]
+    CampaignsByCollection {
+        collection: String,
         start_after: Option<u64>,
         limit: Option<u32>,
     },
 
+    #[returns(Vec<FactoryCampaignResponse>)]
+    CampaignsByRewardToken {
+        reward_token: TokenInfo,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// start_after is a collection address
     #[returns(Vec<String>)]
-    CampaignAddrs {},
+    VerifiedCollections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<TokenInfo>)]
+    VerifiedRewardTokens {
+        start_after: Option<TokenInfo>,
+        limit: Option<u32>,
+    },
+
+    /// address of campaign created by creator with salt
+    #[returns(Addr)]
+    PredictCampaignAddress { creator: String, salt: Binary },
 }
@@ -1,13 +1,20 @@
-use campaign::state::{AssetToken, LockupTerm, TokenInfo};
+use campaign::state::{
+    AssetToken, DistributionMode, EmissionPhase, LockupTerm, LoyaltyStep, RewardMode, TokenInfo,
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[cw_serde]
 pub struct Config {
     pub owner: Addr,
     pub campaign_code_id: u64,
     pub allow_create_for_all: bool,
+    pub creation_fee: Option<AssetToken>, // paid to treasury on create campaign
+    pub treasury: Addr,
+    pub fee_exempt: Vec<Addr>,  // creators not paying creation fee
+    pub protocol_fee_bps: u64,  // fee on claimed reward of new campaigns, paid to treasury
+    pub allow_unverified: bool, // create campaigns of unverified collection or reward token
 }
 
 // We define a custom struct for each query response
@@ -15,6 +22,17 @@ pub struct Config {
 pub struct ConfigResponse {
     pub owner: String,
     pub campaign_code_id: u64,
+    pub creation_fee: Option<AssetToken>,
+    pub treasury: String,
+    pub fee_exempt: Vec<String>,
+    pub protocol_fee_bps: u64,
+    pub allow_unverified: bool,
+}
+
+#[cw_serde]
+pub struct CampaignsResponse {
+    pub campaigns: Vec<FactoryCampaignResponse>,
+    pub next_start_after: Option<u64>, // None when all campaigns are scanned
 }
 
 #[cw_serde]
@@ -23,12 +41,95 @@ pub struct FactoryCampaign {
     pub campaign_addr: Addr,
     pub reward_token: TokenInfo,
     pub allowed_collection: Addr,
+    pub campaign_id: u64,
+    pub start_time: u64, // cached from campaign to filter by status
+    pub end_time: u64,
+    pub cancelled: bool,
+}
+
+#[cw_serde]
+pub struct FactoryCampaignResponse {
+    pub owner: Addr,
+    pub campaign_addr: Addr,
+    pub reward_token: TokenInfo,
+    pub allowed_collection: Addr,
+    pub campaign_id: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub cancelled: bool,
+    pub verified: bool, // collection and reward token are verified, evaluated at query time
+}
+
+#[cw_serde]
+pub enum CampaignStatus {
+    Upcoming,
+    Active,
+    Ended,
+    Cancelled,
+}
+
+impl FactoryCampaign {
+    /// Status of campaign at current time, nft can be staked only when active
+    pub fn status(&self, current_time: u64) -> CampaignStatus {
+        if self.cancelled {
+            CampaignStatus::Cancelled
+        } else if current_time <= self.start_time {
+            CampaignStatus::Upcoming
+        } else if current_time < self.end_time {
+            CampaignStatus::Active
+        } else {
+            CampaignStatus::Ended
+        }
+    }
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const CAMPAIGNS: Map<u64, FactoryCampaign> = Map::new("campaigns");
 pub const NUMBER_OF_CAMPAIGNS: Item<u64> = Item::new("number_of_campaigns");
-pub const ADDR_CAMPAIGNS: Item<Vec<String>> = Item::new("addr_campaigns");
+// Reward sent with cw20 CreateCampaign, forwarded to new campaign in reply
+pub const PENDING_REWARD: Item<Uint128> = Item::new("pending_reward");
+
+// Registries of collections and reward tokens verified by owner
+pub const VERIFIED_COLLECTIONS: Map<&Addr, Empty> = Map::new("verified_collections");
+pub const VERIFIED_REWARD_TOKENS: Map<String, TokenInfo> = Map::new("verified_reward_tokens");
+
+// Mapping from campaign address to campaign id
+pub const CAMPAIGN_IDS: Map<&Addr, u64> = Map::new("campaign_ids");
+
+pub struct CampaignIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, FactoryCampaign, u64>,
+    pub collection: MultiIndex<'a, Addr, FactoryCampaign, u64>,
+    pub reward_token: MultiIndex<'a, String, FactoryCampaign, u64>,
+}
+
+impl<'a> IndexList<FactoryCampaign> for CampaignIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<FactoryCampaign>> + '_> {
+        let v: Vec<&dyn Index<FactoryCampaign>> =
+            vec![&self.owner, &self.collection, &self.reward_token];
+        Box::new(v.into_iter())
+    }
+}
+
+// list campaigns by id, indexed by owner, allowed collection and reward token
+pub fn campaigns<'a>() -> IndexedMap<'a, u64, FactoryCampaign, CampaignIndexes<'a>> {
+    let indexes = CampaignIndexes {
+        owner: MultiIndex::new(
+            |_pk, campaign| campaign.owner.clone(),
+            "campaigns",
+            "campaigns__owner",
+        ),
+        collection: MultiIndex::new(
+            |_pk, campaign| campaign.allowed_collection.clone(),
+            "campaigns",
+            "campaigns__collection",
+        ),
+        reward_token: MultiIndex::new(
+            |_pk, campaign| campaign.reward_token.to_string(),
+            "campaigns",
+            "campaigns__reward_token",
+        ),
+    };
+    IndexedMap::new("campaigns", indexes)
+}
 
 #[cw_serde]
 pub struct Trait {
@@ -71,4 +172,12 @@ pub struct CreateCampaign {
     pub reward_token_info: AssetToken,
     pub allowed_collection: String,
     pub lockup_term: Vec<LockupTerm>,
+    pub distribution_mode: Option<DistributionMode>,
+    pub loyalty_steps: Option<Vec<LoyaltyStep>>,
+    pub emission_schedule: Option<Vec<EmissionPhase>>,
+    pub rollover_unallocated: Option<bool>,
+    pub receipt_code_id: Option<u64>,
+    pub reward_mode: Option<RewardMode>,
+    pub soft_staking: Option<bool>,
+    pub voting_power_by_lockup: Option<bool>,
 }
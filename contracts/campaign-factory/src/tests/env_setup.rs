@@ -1,8 +1,12 @@
 #[cfg(test)]
 pub mod env {
-    use cosmwasm_std::{Addr, Empty};
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+        StdResult,
+    };
     use cw20::MinterResponse;
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cw_storage_plus::Item;
 
     use crate::contract::{
         execute as FactoryExecute, instantiate as FactoryInstantiate, query as FactoryQuery,
@@ -19,9 +23,12 @@ pub mod env {
         execute as Cw721Execute, instantiate as Cw721Instantiate, query as Cw721Query,
     };
 
-    use cw721_base::msg::InstantiateMsg as Cw721InstantiateMsg;
+    use cw721_base::msg::{InstantiateMsg as Cw721InstantiateMsg, QueryMsg as Cw721QueryMsg};
 
-    use campaign::contract::{execute as Execute, instantiate as Instantiate, query as Query};
+    use campaign::contract::{
+        execute as Execute, instantiate as Instantiate, query as Query, reply as Reply,
+    };
+    use campaign::msg::{StakeChangedExecuteMsg, StakeChangedHookMsg};
 
     use crate::msg::InstantiateMsg as FactoryInstantiateMsg;
 
@@ -30,6 +37,7 @@ pub mod env {
     pub const USER_2: &str = "aura1000000000000000000000000000000000user2";
     pub const USER_3: &str = "aura1000000000000000000000000000000000user3";
 
+    #[allow(dead_code)]
     pub struct ContractInfo {
         pub contract_addr: String,
         pub contract_code_id: u64,
@@ -53,7 +61,7 @@ pub mod env {
 
     // campaign contract
     fn contract_template() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(Execute, Instantiate, Query);
+        let contract = ContractWrapper::new(Execute, Instantiate, Query).with_reply(Reply);
         Box::new(contract)
     }
 
@@ -70,6 +78,83 @@ pub mod env {
         Box::new(contract)
     }
 
+    // collection contract failing every query while it is paused by sudo
+    const COLLECTION_PAUSED: Item<bool> = Item::new("collection_paused");
+
+    fn pausable_collection_sudo(deps: DepsMut, _env: Env, paused: bool) -> StdResult<Response> {
+        COLLECTION_PAUSED.save(deps.storage, &paused)?;
+        Ok(Response::new())
+    }
+
+    fn pausable_collection_query(
+        deps: Deps,
+        env: Env,
+        msg: Cw721QueryMsg<Empty>,
+    ) -> StdResult<Binary> {
+        if COLLECTION_PAUSED.may_load(deps.storage)?.unwrap_or(false) {
+            return Err(StdError::generic_err("collection is paused"));
+        }
+        Cw721Query(deps, env, msg)
+    }
+
+    pub fn pausable_collection_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract =
+            ContractWrapper::new(Cw721Execute, Cw721Instantiate, pausable_collection_query)
+                .with_sudo(pausable_collection_sudo);
+        Box::new(contract)
+    }
+
+    // hook contract, records every stake changed msg it receives
+    const HOOK_MSGS: Item<Vec<StakeChangedHookMsg>> = Item::new("hook_msgs");
+
+    fn hook_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        HOOK_MSGS.save(deps.storage, &vec![])?;
+        Ok(Response::new())
+    }
+
+    fn hook_execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: StakeChangedExecuteMsg,
+    ) -> StdResult<Response> {
+        let StakeChangedExecuteMsg::StakeChangeHook(hook_msg) = msg;
+        HOOK_MSGS.update(deps.storage, |mut msgs| -> StdResult<_> {
+            msgs.push(hook_msg);
+            Ok(msgs)
+        })?;
+        Ok(Response::new())
+    }
+
+    fn hook_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_binary(&HOOK_MSGS.load(deps.storage)?)
+    }
+
+    pub fn hook_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(hook_execute, hook_instantiate, hook_query);
+        Box::new(contract)
+    }
+
+    // hook contract failing on every stake changed msg
+    fn failing_hook_execute(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: StakeChangedExecuteMsg,
+    ) -> StdResult<Response> {
+        Err(StdError::generic_err("hook failed"))
+    }
+
+    pub fn failing_hook_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(failing_hook_execute, hook_instantiate, hook_query);
+        Box::new(contract)
+    }
+
     pub fn instantiate_contracts() -> (App, Vec<ContractInfo>) {
         // Create a new app instance
         let mut app = mock_app();
@@ -86,6 +171,11 @@ pub mod env {
         let factory_instantiate_msg = FactoryInstantiateMsg {
             campaign_code_id: app.store_code(contract_template()),
             allow_create_for_all: false,
+            creation_fee: None,
+            treasury: None,
+            fee_exempt: None,
+            protocol_fee_bps: None,
+            allow_unverified: None,
         };
 
         // factory instantiate contract
@@ -11,25 +11,39 @@ mod tests {
     // 8. withdraw remaining reward
     mod execute_proper_operation {
         use crate::{
+            error::ContractError,
             msg::QueryMsg,
-            state::{ConfigResponse, CreateCampaign, FactoryCampaign, Metadata},
+            state::{
+                CampaignStatus, CampaignsResponse, ConfigResponse, CreateCampaign,
+                FactoryCampaignResponse, Metadata,
+            },
             tests::{
-                env_setup::env::{instantiate_contracts, ADMIN, USER_1, USER_2, USER_3},
+                env_setup::env::{
+                    failing_hook_contract_template, hook_contract_template, instantiate_contracts,
+                    pausable_collection_contract_template, ADMIN, USER_1, USER_2, USER_3,
+                },
                 integration_test::tests::MOCK_1000_TOKEN_AMOUNT,
             },
         };
         use campaign::state::{
-            AssetToken, CampaignInfoResult, LockupTerm, NftInfo, NftStake, StakedInfoResult,
-            TokenInfo,
+            AssetToken, CampaignInfoResult, DistributionMode, EmissionPhase, EmissionPhaseResult,
+            EmissionScheduleResult, HooksResponse, LockupTerm, LoyaltyStep, NftInfo, NftStake,
+            RewardMode, SimulateStakeResult, SimulatedNftResult, SolvencyResult, StakedInfoResult,
+            StakerInfoResult, TokenInfo, TotalPowerAtHeightResponse, UnallocatedRewardResult,
+            VotingPowerAtHeightResponse,
         };
         use campaign::{
-            msg::{ExecuteMsg as CampaignExecuteMsg, QueryMsg as CampaignQueryMsg},
-            utils::{add_reward, calc_reward_in_time, sub_reward},
+            msg::{
+                ExecuteMsg as CampaignExecuteMsg, QueryMsg as CampaignQueryMsg, StakeChangedHookMsg,
+            },
+            utils::{add_reward, sub_reward},
+            ContractError as CampaignContractError,
         };
-        use cosmwasm_std::{Addr, BlockInfo, Empty, Uint128};
-        use cw20::{BalanceResponse, Cw20ExecuteMsg};
+        use cosmwasm_std::{coins, to_binary, Addr, BlockInfo, Empty, Uint128};
+        use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg};
         use cw721_base::MintMsg as Cw721MintMsg;
-        use cw_multi_test::Executor;
+        use cw_multi_test::{App, BankSudo, Executor, SudoMsg};
+        use cw_utils::PaymentError;
 
         pub type Extension = Option<Metadata>;
         pub type Cw721ExecuteMsg = cw721_base::ExecuteMsg<Extension, Empty>;
@@ -92,6 +106,11 @@ mod tests {
                 ConfigResponse {
                     owner: ADMIN.to_string(),
                     campaign_code_id: 4,
+                    creation_fee: None,
+                    treasury: ADMIN.to_string(),
+                    fee_exempt: vec![],
+                    protocol_fee_bps: 0,
+                    allow_unverified: true,
                 }
             );
 
@@ -100,6 +119,11 @@ mod tests {
                 owner: Some(Addr::unchecked(USER_1)),
                 campaign_code_id: None,
                 allow_create_for_all: None,
+                creation_fee: None,
+                treasury: None,
+                fee_exempt: None,
+                protocol_fee_bps: None,
+                allow_unverified: None,
             };
 
             // Execute update config
@@ -127,6 +151,11 @@ mod tests {
                 owner: Some(Addr::unchecked(ADMIN)),
                 campaign_code_id: None,
                 allow_create_for_all: None,
+                creation_fee: None,
+                treasury: None,
+                fee_exempt: None,
+                protocol_fee_bps: None,
+                allow_unverified: None,
             };
 
             // Execute update config
@@ -295,7 +324,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -309,7 +347,7 @@ mod tests {
             assert!(response_create_campaign.is_ok());
 
             // query campaign contract address
-            let campaign_info: FactoryCampaign = app
+            let campaign_info: FactoryCampaignResponse = app
                 .wrap()
                 .query_wasm_smart(
                     factory_contract.clone(),
@@ -320,13 +358,18 @@ mod tests {
             // assert campaign info
             assert_eq!(
                 campaign_info,
-                FactoryCampaign {
+                FactoryCampaignResponse {
                     owner: Addr::unchecked(ADMIN.to_string()),
                     campaign_addr: Addr::unchecked("contract3"),
                     reward_token: TokenInfo::Token {
                         contract_addr: token_contract.to_string()
                     },
-                    allowed_collection: Addr::unchecked(collection_contract)
+                    allowed_collection: Addr::unchecked(collection_contract),
+                    campaign_id: 1,
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    cancelled: false,
+                    verified: false,
                 }
             );
 
@@ -335,7 +378,10 @@ mod tests {
                 .wrap()
                 .query_wasm_smart(
                     factory_contract.clone(),
-                    &crate::msg::QueryMsg::CampaignAddrs {},
+                    &crate::msg::QueryMsg::CampaignAddrs {
+                        start_after: None,
+                        limit: None,
+                    },
                 )
                 .unwrap();
 
@@ -381,6 +427,19 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 0,
+                    total_stakers: 0,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
@@ -424,31 +483,51 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 0,
+                    total_stakers: 0,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
             // query all campaigns in factory contract
-            let campaigns: Vec<FactoryCampaign> = app
+            let campaigns: Vec<FactoryCampaignResponse> = app
                 .wrap()
-                .query_wasm_smart(
+                .query_wasm_smart::<CampaignsResponse>(
                     Addr::unchecked(factory_contract.clone()),
                     &QueryMsg::Campaigns {
+                        status: None,
                         start_after: None,
                         limit: None,
                     },
                 )
-                .unwrap();
+                .unwrap()
+                .campaigns;
 
             // TODO: contract3 unknown ?
             assert_eq!(
                 campaigns,
-                vec![FactoryCampaign {
+                vec![FactoryCampaignResponse {
                     owner: Addr::unchecked(ADMIN.to_string()),
                     campaign_addr: Addr::unchecked("contract3"),
                     reward_token: TokenInfo::Token {
                         contract_addr: token_contract.to_string()
                     },
-                    allowed_collection: Addr::unchecked(collection_contract)
+                    allowed_collection: Addr::unchecked(collection_contract),
+                    campaign_id: 1,
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    cancelled: false,
+                    verified: false,
                 }]
             );
 
@@ -556,6 +635,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "1".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -583,6 +663,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -603,7 +686,9 @@ mod tests {
                         end_time: start_time_1 + 10
                     }],
                     reward_debt: Uint128::zero(),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 }
             );
 
@@ -621,6 +706,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "1".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -667,6 +753,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "1".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -694,6 +781,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "2".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -728,6 +816,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -762,13 +853,16 @@ mod tests {
                         },
                     ],
                     reward_debt: Uint128::from(21000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 }
             );
 
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(21000u128),
+                receipt_ids: None,
             };
 
             // Execute claim reward
@@ -788,6 +882,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -823,6 +920,8 @@ mod tests {
                     ],
                     reward_debt: Uint128::from(0u128),
                     reward_claimed: Uint128::from(21000u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(21000u128),
                 }
             );
 
@@ -855,6 +954,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -876,13 +978,18 @@ mod tests {
                     },],
                     reward_debt: Uint128::from(9000u128),
                     reward_claimed: Uint128::from(21000u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(21000u128),
                 }
             );
 
             // get staker total pending reward
             let total_pending_reward: Uint128 = app
                 .wrap()
-                .query_wasm_smart("contract3", &CampaignQueryMsg::TotalPendingReward {})
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward { at_time: None },
+                )
                 .unwrap();
 
             // token_id 2 = 4500, reward_debt USER_1 = 4500(token_id 1 unstake transerfered)
@@ -902,6 +1009,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -922,14 +1032,19 @@ mod tests {
                         end_time: start_time_2 + 10
                     },],
                     reward_debt: Uint128::from(12000u128),
-                    reward_claimed: Uint128::from(21000u128)
+                    reward_claimed: Uint128::from(21000u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(21000u128),
                 }
             );
 
             // get total pending reward
             let total_pending_reward: Uint128 = app
                 .wrap()
-                .query_wasm_smart("contract3", &CampaignQueryMsg::TotalPendingReward {})
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward { at_time: None },
+                )
                 .unwrap();
 
             // token_id 2 = 7500, reward_debt USER_1 = 4500(token_id 1 unstake transerfered)
@@ -988,6 +1103,19 @@ mod tests {
                     time_calc_nft: current_block_time + 110,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 1,
+                    total_stakers: 1,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
@@ -998,6 +1126,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1019,13 +1150,18 @@ mod tests {
                     },],
                     reward_debt: Uint128::from(12000u128),
                     reward_claimed: Uint128::from(21000u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(21000u128),
                 }
             );
 
             // get staker total pending reward
             let total_pending_reward: Uint128 = app
                 .wrap()
-                .query_wasm_smart("contract3", &CampaignQueryMsg::TotalPendingReward {})
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward { at_time: None },
+                )
                 .unwrap();
 
             assert_eq!(total_pending_reward, Uint128::from(12000u128));
@@ -1250,7 +1386,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -1264,7 +1409,7 @@ mod tests {
             assert!(response_create_campaign.is_ok());
 
             // query campaign contract address
-            let campaign_info: FactoryCampaign = app
+            let campaign_info: FactoryCampaignResponse = app
                 .wrap()
                 .query_wasm_smart(
                     factory_contract.clone(),
@@ -1275,13 +1420,18 @@ mod tests {
             // assert campaign info
             assert_eq!(
                 campaign_info,
-                FactoryCampaign {
+                FactoryCampaignResponse {
                     owner: Addr::unchecked(ADMIN.to_string()),
                     campaign_addr: Addr::unchecked("contract3"),
                     reward_token: TokenInfo::Token {
                         contract_addr: token_contract.to_string()
                     },
-                    allowed_collection: Addr::unchecked(collection_contract)
+                    allowed_collection: Addr::unchecked(collection_contract),
+                    campaign_id: 1,
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    cancelled: false,
+                    verified: false,
                 }
             );
 
@@ -1325,6 +1475,19 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 0,
+                    total_stakers: 0,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
@@ -1368,31 +1531,51 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 0,
+                    total_stakers: 0,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
             // query all campaigns in factory contract
-            let campaigns: Vec<FactoryCampaign> = app
+            let campaigns: Vec<FactoryCampaignResponse> = app
                 .wrap()
-                .query_wasm_smart(
+                .query_wasm_smart::<CampaignsResponse>(
                     Addr::unchecked(factory_contract.clone()),
                     &QueryMsg::Campaigns {
+                        status: None,
                         start_after: None,
                         limit: None,
                     },
                 )
-                .unwrap();
+                .unwrap()
+                .campaigns;
 
             // TODO: contract3 unknown ?
             assert_eq!(
                 campaigns,
-                vec![FactoryCampaign {
+                vec![FactoryCampaignResponse {
                     owner: Addr::unchecked(ADMIN.to_string()),
                     campaign_addr: Addr::unchecked("contract3"),
                     reward_token: TokenInfo::Token {
                         contract_addr: token_contract.to_string()
                     },
-                    allowed_collection: Addr::unchecked(collection_contract)
+                    allowed_collection: Addr::unchecked(collection_contract),
+                    campaign_id: 1,
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    cancelled: false,
+                    verified: false,
                 }]
             );
 
@@ -1519,6 +1702,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "1".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1546,6 +1730,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "2".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1573,6 +1758,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1607,7 +1795,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::zero(),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -1644,6 +1834,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1678,7 +1871,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(15000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -1689,6 +1884,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1709,7 +1907,9 @@ mod tests {
                         end_time: start_time_6 + 30
                     }],
                     reward_debt: Uint128::zero(),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -1746,6 +1946,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1792,7 +1995,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(30000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -1803,6 +2008,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1823,7 +2031,9 @@ mod tests {
                         end_time: start_time_6 + 30
                     }],
                     reward_debt: Uint128::from(35000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -1841,6 +2051,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1887,13 +2100,16 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(47500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(47500u128),
+                receipt_ids: None,
             };
 
             // Execute claim reward
@@ -1913,6 +2129,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -1959,7 +2178,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(0u128),
-                    reward_claimed: Uint128::from(47500u128)
+                    reward_claimed: Uint128::from(47500u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(47500u128),
                 },
             );
 
@@ -1996,6 +2217,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "7".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2058,16 +2280,50 @@ mod tests {
                     time_calc_nft: current_block_time + 90,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 2,
+                    total_stakers: 2,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
             // query token_ids
             let token_ids: Vec<String> = app
                 .wrap()
-                .query_wasm_smart(Addr::unchecked("contract3"), &CampaignQueryMsg::TokenIds {})
+                .query_wasm_smart(
+                    Addr::unchecked("contract3"),
+                    &CampaignQueryMsg::TokenIds {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            // token_ids are listed in order of token_id
+            assert_eq!(token_ids, vec!["1", "2", "3", "6", "7"]);
+
+            // query next page of token_ids
+            let token_ids: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    Addr::unchecked("contract3"),
+                    &CampaignQueryMsg::TokenIds {
+                        start_after: Some("2".to_string()),
+                        limit: Some(2),
+                    },
+                )
                 .unwrap();
 
-            assert_eq!(token_ids, vec!["1", "2", "6", "3", "7"]);
+            assert_eq!(token_ids, vec!["3", "6"]);
 
             // stake nft token_id 8
             let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
@@ -2128,6 +2384,19 @@ mod tests {
                     time_calc_nft: current_block_time + 90,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 2,
+                    total_stakers: 2,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
@@ -2167,6 +2436,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2175,18 +2447,6 @@ mod tests {
                 staked,
                 StakedInfoResult {
                     nfts: vec![
-                        NftInfo {
-                            token_id: "8".to_string(),
-                            owner: Addr::unchecked(USER_2.to_string()),
-                            pending_reward: Uint128::from(0u128),
-                            lockup_term: LockupTerm {
-                                value: 10,
-                                percent: Uint128::from(30u128)
-                            },
-                            is_end_reward: false,
-                            start_time: start_time_8,
-                            end_time: start_time_8 + 10
-                        },
                         NftInfo {
                             token_id: "6".to_string(),
                             owner: Addr::unchecked(USER_2.to_string()),
@@ -2210,13 +2470,63 @@ mod tests {
                             is_end_reward: false,
                             start_time: start_time_7,
                             end_time: start_time_7 + 30
+                        },
+                        NftInfo {
+                            token_id: "8".to_string(),
+                            owner: Addr::unchecked(USER_2.to_string()),
+                            pending_reward: Uint128::from(0u128),
+                            lockup_term: LockupTerm {
+                                value: 10,
+                                percent: Uint128::from(30u128)
+                            },
+                            is_end_reward: false,
+                            start_time: start_time_8,
+                            end_time: start_time_8 + 10
                         }
                     ],
                     reward_debt: Uint128::from(122500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
+            // next page of nft staked with USER_2
+            let staked: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: Some("6".to_string()),
+                        limit: Some(1),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            // reward_debt is still counted from all nfts of staker
+            assert_eq!(staked.nfts.len(), 1);
+            assert_eq!(staked.nfts[0].token_id, "7".to_string());
+            assert_eq!(staked.reward_debt, Uint128::from(122500u128));
+
+            // nfts in lockup term 10
+            let nfts: Vec<NftInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::Nfts {
+                        lockup_term: Some(10),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert!(nfts.iter().all(|nft| nft.lockup_term.value == 10));
+            assert!(nfts.iter().any(|nft| nft.token_id == "8"));
+
             // get nft info id 7
             let nft_info: NftInfo = app
                 .wrap()
@@ -2224,6 +2534,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "7".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2251,6 +2562,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "8".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2278,6 +2590,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2286,18 +2601,6 @@ mod tests {
                 staked,
                 StakedInfoResult {
                     nfts: vec![
-                        NftInfo {
-                            token_id: "8".to_string(),
-                            owner: Addr::unchecked(USER_2.to_string()),
-                            pending_reward: Uint128::from(0u128),
-                            lockup_term: LockupTerm {
-                                value: 10,
-                                percent: Uint128::from(30u128)
-                            },
-                            is_end_reward: false,
-                            start_time: start_time_8,
-                            end_time: start_time_8 + 10
-                        },
                         NftInfo {
                             token_id: "6".to_string(),
                             owner: Addr::unchecked(USER_2.to_string()),
@@ -2321,10 +2624,24 @@ mod tests {
                             is_end_reward: false,
                             start_time: start_time_7,
                             end_time: start_time_7 + 30
+                        },
+                        NftInfo {
+                            token_id: "8".to_string(),
+                            owner: Addr::unchecked(USER_2.to_string()),
+                            pending_reward: Uint128::from(0u128),
+                            lockup_term: LockupTerm {
+                                value: 10,
+                                percent: Uint128::from(30u128)
+                            },
+                            is_end_reward: false,
+                            start_time: start_time_8,
+                            end_time: start_time_8 + 10
                         }
                     ],
                     reward_debt: Uint128::from(122500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -2368,6 +2685,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2426,7 +2746,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(140000u128),
-                    reward_claimed: Uint128::from(47500u128)
+                    reward_claimed: Uint128::from(47500u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(47500u128),
                 },
             );
 
@@ -2437,6 +2759,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2445,18 +2770,6 @@ mod tests {
                 staked,
                 StakedInfoResult {
                     nfts: vec![
-                        NftInfo {
-                            token_id: "8".to_string(),
-                            owner: Addr::unchecked(USER_2.to_string()),
-                            pending_reward: Uint128::from(30000u128),
-                            lockup_term: LockupTerm {
-                                value: 10,
-                                percent: Uint128::from(30u128)
-                            },
-                            is_end_reward: true,
-                            start_time: start_time_8,
-                            end_time: start_time_8 + 10
-                        },
                         NftInfo {
                             token_id: "6".to_string(),
                             owner: Addr::unchecked(USER_2.to_string()),
@@ -2480,10 +2793,24 @@ mod tests {
                             is_end_reward: true,
                             start_time: start_time_7,
                             end_time: start_time_7 + 30
+                        },
+                        NftInfo {
+                            token_id: "8".to_string(),
+                            owner: Addr::unchecked(USER_2.to_string()),
+                            pending_reward: Uint128::from(30000u128),
+                            lockup_term: LockupTerm {
+                                value: 10,
+                                percent: Uint128::from(30u128)
+                            },
+                            is_end_reward: true,
+                            start_time: start_time_8,
+                            end_time: start_time_8 + 10
                         }
                     ],
                     reward_debt: Uint128::from(257500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -2599,6 +2926,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2608,7 +2938,9 @@ mod tests {
                 StakedInfoResult {
                     nfts: vec![],
                     reward_debt: Uint128::from(140000u128),
-                    reward_claimed: Uint128::from(47500u128)
+                    reward_claimed: Uint128::from(47500u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(47500u128),
                 },
             );
 
@@ -2619,6 +2951,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -2628,14 +2963,19 @@ mod tests {
                 StakedInfoResult {
                     nfts: vec![],
                     reward_debt: Uint128::from(257500u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
             // get staker total pending reward
             let total_pending_reward: Uint128 = app
                 .wrap()
-                .query_wasm_smart("contract3", &CampaignQueryMsg::TotalPendingReward {})
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward { at_time: None },
+                )
                 .unwrap();
 
             // USER_1 = 140000, USER_2 = 257500
@@ -2721,6 +3061,19 @@ mod tests {
                     time_calc_nft: current_block_time + 110,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 0,
+                    total_stakers: 2,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
         }
@@ -2934,7 +3287,16 @@ mod tests {
                         value: 30,
                         percent: Uint128::new(100u128),
                     }],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -2948,7 +3310,7 @@ mod tests {
             assert!(response_create_campaign.is_ok());
 
             // query campaign contract address
-            let campaign_info: FactoryCampaign = app
+            let campaign_info: FactoryCampaignResponse = app
                 .wrap()
                 .query_wasm_smart(
                     factory_contract.clone(),
@@ -2959,13 +3321,18 @@ mod tests {
             // assert campaign info
             assert_eq!(
                 campaign_info,
-                FactoryCampaign {
+                FactoryCampaignResponse {
                     owner: Addr::unchecked(ADMIN.to_string()),
                     campaign_addr: Addr::unchecked("contract3"),
                     reward_token: TokenInfo::Token {
                         contract_addr: token_contract.to_string()
                     },
-                    allowed_collection: Addr::unchecked(collection_contract)
+                    allowed_collection: Addr::unchecked(collection_contract),
+                    campaign_id: 1,
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    cancelled: false,
+                    verified: false,
                 }
             );
 
@@ -3003,31 +3370,51 @@ mod tests {
                     time_calc_nft: 0,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 0,
+                    total_stakers: 0,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
             // query all campaigns in factory contract
-            let campaigns: Vec<FactoryCampaign> = app
+            let campaigns: Vec<FactoryCampaignResponse> = app
                 .wrap()
-                .query_wasm_smart(
+                .query_wasm_smart::<CampaignsResponse>(
                     Addr::unchecked(factory_contract.clone()),
                     &QueryMsg::Campaigns {
+                        status: None,
                         start_after: None,
                         limit: None,
                     },
                 )
-                .unwrap();
+                .unwrap()
+                .campaigns;
 
             // TODO: contract3 unknown ?
             assert_eq!(
                 campaigns,
-                vec![FactoryCampaign {
+                vec![FactoryCampaignResponse {
                     owner: Addr::unchecked(ADMIN.to_string()),
                     campaign_addr: Addr::unchecked("contract3"),
                     reward_token: TokenInfo::Token {
                         contract_addr: token_contract.to_string()
                     },
-                    allowed_collection: Addr::unchecked(collection_contract)
+                    allowed_collection: Addr::unchecked(collection_contract),
+                    campaign_id: 1,
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    cancelled: false,
+                    verified: false,
                 }]
             );
 
@@ -3161,6 +3548,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "1".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3188,6 +3576,7 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftInfo {
                         token_id: "2".to_string(),
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3215,6 +3604,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3249,7 +3641,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(100000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3279,6 +3673,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3299,7 +3696,9 @@ mod tests {
                         end_time: start_time_6 + 30
                     },],
                     reward_debt: Uint128::from(0u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3317,6 +3716,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3350,8 +3752,11 @@ mod tests {
                             end_time: start_time_2 + 30
                         }
                     ],
-                    reward_debt: Uint128::from(233332u128),
-                    reward_claimed: Uint128::zero()
+                    // rounding remainders of nfts add up in reward of staker
+                    reward_debt: Uint128::from(233333u128),
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3362,6 +3767,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3382,7 +3790,9 @@ mod tests {
                         end_time: start_time_6 + 30
                     },],
                     reward_debt: Uint128::from(66666u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3400,6 +3810,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3433,8 +3846,11 @@ mod tests {
                             end_time: start_time_2 + 30
                         }
                     ],
-                    reward_debt: Uint128::from(283332u128),
-                    reward_claimed: Uint128::zero()
+                    // rounding remainders of nfts add up in reward of staker
+                    reward_debt: Uint128::from(283333u128),
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3445,6 +3861,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3465,13 +3884,16 @@ mod tests {
                         end_time: start_time_6 + 30
                     },],
                     reward_debt: Uint128::from(116666u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(283332u128),
+                receipt_ids: None,
             };
 
             // Execute claim reward
@@ -3491,6 +3913,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3524,8 +3949,11 @@ mod tests {
                             end_time: start_time_2 + 30
                         }
                     ],
-                    reward_debt: Uint128::from(0u128),
+                    // rounding remainder of claimed reward stays with staker
+                    reward_debt: Uint128::from(1u128),
                     reward_claimed: Uint128::from(283332u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(283332u128),
                 },
             );
 
@@ -3588,6 +4016,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3622,7 +4053,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(216666u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3640,6 +4073,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3674,7 +4110,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(266666u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3685,6 +4123,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_3.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3705,7 +4146,9 @@ mod tests {
                         end_time: start_time_11 + 30
                     }],
                     reward_debt: Uint128::from(50000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3791,6 +4234,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3799,8 +4245,10 @@ mod tests {
                 staked,
                 StakedInfoResult {
                     nfts: vec![],
-                    reward_debt: Uint128::from(0u128),
+                    reward_debt: Uint128::from(1u128),
                     reward_claimed: Uint128::from(283332u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(283332u128),
                 },
             );
 
@@ -3811,6 +4259,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3820,7 +4271,9 @@ mod tests {
                 StakedInfoResult {
                     nfts: vec![],
                     reward_debt: Uint128::from(266666u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -3831,6 +4284,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_3.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -3840,20 +4296,25 @@ mod tests {
                 StakedInfoResult {
                     nfts: vec![],
                     reward_debt: Uint128::from(50000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
             // get staker total pending reward
             let total_pending_reward: Uint128 = app
                 .wrap()
-                .query_wasm_smart("contract3", &CampaignQueryMsg::TotalPendingReward {})
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward { at_time: None },
+                )
                 .unwrap();
 
-            // USER_2 = 266666, USER_3 = 50000
-            assert_eq!(total_pending_reward, Uint128::from(316666u128));
+            // USER_1 = 1, USER_2 = 266666, USER_3 = 50000
+            assert_eq!(total_pending_reward, Uint128::from(316667u128));
 
-            // withdraw remaining reward msg = 1000000 - 283332 - 316666 = 400002
+            // withdraw remaining reward msg = 1000000 - 283332 - 316667 = 400001
             let withdraw_reward_msg = CampaignExecuteMsg::WithdrawReward {};
 
             // Execute withdraw remaining reward
@@ -3886,7 +4347,7 @@ mod tests {
                     limit_per_staker: 4,
                     reward_token_info: AssetToken {
                         info: token_info,
-                        amount: Uint128::from(316666u128),
+                        amount: Uint128::from(316667u128),
                     },
                     allowed_collection: Addr::unchecked(collection_contract.clone()),
                     lockup_term: vec![LockupTerm {
@@ -3900,6 +4361,19 @@ mod tests {
                     time_calc_nft: current_block_time + 110,
                     start_time: current_block_time + 10,
                     end_time: current_block_time + 110,
+                    distribution_mode: DistributionMode::Linear,
+                    loyalty_steps: vec![],
+                    emission_schedule: vec![],
+                    rollover_unallocated: false,
+                    reward_dust: Uint128::zero(),
+                    current_stakers: 0,
+                    total_stakers: 3,
+                    receipt_collection: None,
+                    reward_mode: RewardMode::Transfer,
+                    soft_staking: false,
+                    voting_power_by_lockup: false,
+                    protocol_fee_bps: 0,
+                    protocol_fee_recipient: Some(Addr::unchecked(ADMIN.to_string())),
                 }
             );
 
@@ -3915,7 +4389,7 @@ mod tests {
                 .unwrap();
 
             // It should be 555000 token as withdraw happened
-            assert_eq!(balance.balance, Uint128::from(400002u128));
+            assert_eq!(balance.balance, Uint128::from(400001u128));
 
             // query balance of campaign contract in cw20 base token contract
             let balance: BalanceResponse = app
@@ -3928,7 +4402,7 @@ mod tests {
                 )
                 .unwrap();
 
-            assert_eq!(balance.balance, Uint128::from(316666u128));
+            assert_eq!(balance.balance, Uint128::from(316667u128));
         }
 
         //         -------------- wrong operation ------------------
@@ -4123,7 +4597,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4141,6 +4624,11 @@ mod tests {
                 owner: None,
                 campaign_code_id: None,
                 allow_create_for_all: Some(true),
+                creation_fee: None,
+                treasury: None,
+                fee_exempt: None,
+                protocol_fee_bps: None,
+                allow_unverified: None,
             };
 
             // Execute update config
@@ -4217,7 +4705,19 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: Some(RewardMode::Mint {
+                        reward_per_second: Uint128::from(2u128),
+                        mint_cap: None,
+                    }),
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4227,7 +4727,7 @@ mod tests {
                 &create_campaign_msg,
                 &[],
             );
-            // wrong with token is native token
+            // wrong with native token can not be minted
             assert!(response_create_campaign.is_err());
 
             // create campaign contract by factory contract
@@ -4255,7 +4755,16 @@ mod tests {
                             percent: Uint128::new(80u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4293,7 +4802,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4331,7 +4849,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4369,7 +4896,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4407,7 +4943,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4445,7 +4990,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4483,7 +5037,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -4627,6 +5190,7 @@ mod tests {
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(20000u128),
+                receipt_ids: None,
             };
 
             // Execute claim reward
@@ -4753,6 +5317,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -4811,13 +5378,16 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(15000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
             // USER_1 claim reward msg
             let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
                 amount: Uint128::from(20000u128),
+                receipt_ids: None,
             };
 
             // Execute claim reward
@@ -4845,6 +5415,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -4903,7 +5476,9 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(30000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
@@ -4945,6 +5520,9 @@ mod tests {
                     "contract3",
                     &CampaignQueryMsg::NftStaked {
                         owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
                     },
                 )
                 .unwrap();
@@ -4991,13 +5569,23 @@ mod tests {
                         }
                     ],
                     reward_debt: Uint128::from(30000u128),
-                    reward_claimed: Uint128::zero()
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
                 },
             );
 
             let nfts: Vec<NftInfo> = app
                 .wrap()
-                .query_wasm_smart("contract3", &CampaignQueryMsg::Nfts { limit: None })
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::Nfts {
+                        lockup_term: None,
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
                 .unwrap();
 
             assert_eq!(
@@ -5113,23 +5701,7 @@ mod tests {
         // overflow when sub reward
         #[test]
         fn utils_test_function() {
-            let start_time: u64 = 10;
-            let end_time: u64 = 20;
-            let reward_per_second: Uint128 = Uint128::from(10u128);
-            let percent: Uint128 = Uint128::from(70u128);
-            let nft_count: u128 = 1;
-
-            // check response calc_reward_in_time
-            let response =
-                calc_reward_in_time(start_time, end_time, reward_per_second, percent, nft_count);
-            assert!(response.is_ok());
-
-            let calc_reward = response.unwrap();
-            assert_eq!(calc_reward, Uint128::from(70u128));
-
-            // check response calc_reward_in_time error
-            let response = calc_reward_in_time(start_time, end_time, reward_per_second, percent, 0);
-            assert!(response.is_err());
+            let calc_reward = Uint128::from(70u128);
 
             // add_reward
             let response = add_reward(Uint128::zero(), calc_reward);
@@ -5328,7 +5900,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -5513,7 +6094,16 @@ mod tests {
                             percent: Uint128::new(70u128),
                         },
                     ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
                 },
+                salt: None,
             };
 
             // Execute create campaign
@@ -5598,5 +6188,8520 @@ mod tests {
 
             assert!(response.is_err());
         }
+
+        // -------------- typed errors ------------------
+        // - invalid owner of campaign and invalid hook address fail with InvalidAddress
+        // - lockup term percents overflowing Uint128 fail with Overflow
+        // - checked division by zero maps to DivideByZero with its context
+        #[test]
+        fn wrong_operation_typed_errors() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 100,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // owner of campaign is not a valid address
+            let mut invalid_owner_campaign = create_campaign.clone();
+            invalid_owner_campaign.owner = "INVALID_OWNER".to_string();
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: invalid_owner_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response.unwrap_err().downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::InvalidAddress { address, .. }) if address == "INVALID_OWNER"
+            ));
+
+            // sum of lockup term percents overflows
+            let mut overflow_campaign = create_campaign.clone();
+            overflow_campaign.lockup_term = vec![
+                LockupTerm {
+                    value: 10,
+                    percent: Uint128::MAX,
+                },
+                LockupTerm {
+                    value: 30,
+                    percent: Uint128::one(),
+                },
+            ];
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: overflow_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response.unwrap_err().downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::Overflow { context, .. }) if context == "sum lockup term percent"
+            ));
+
+            // valid campaign is created
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // hook is not a valid address
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddHook {
+                    addr: "INVALID_HOOK".to_string(),
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response.unwrap_err().downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::InvalidAddress { address, .. }) if address == "INVALID_HOOK"
+            ));
+
+            // division by zero keeps its context
+            let response = Uint128::one().checked_div(Uint128::zero()).map_err(
+                CampaignContractError::divide_by_zero("calc reward per second"),
+            );
+
+            assert!(matches!(
+                response,
+                Err(CampaignContractError::DivideByZero { context, .. }) if context == "calc reward per second"
+            ));
+        }
+
+        #[test]
+        fn wrong_operation_stake_empty_nfts() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake an empty list of nft
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts { nfts: vec![] },
+                &[],
+            );
+
+            assert!(matches!(
+                response
+                    .unwrap_err()
+                    .downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::EmptyNftList {})
+            ));
+
+            // USER_1 is not counted as staker
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.current_stakers, 0);
+            assert_eq!(campaign_info.total_stakers, 0);
+
+            let stakers: Vec<StakerInfoResult> = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::Stakers {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(stakers, vec![]);
+        }
+
+        // -------------- square root distribution ------------------
+        // - ADMIN create campaign with distribution_mode = SquareRoot
+        // - add 1000.000 reward token, end time 100s -> reward_per_second = 10.000 token
+        // - one lockup_term = 50s, percent = 100%
+        // - USER_1 stake 4 nfts, USER_2 stake 1 nft at the same time
+        // - increase simulation time more 10s -> reward = 10(s) * 10.000 = 100.000
+        // 	- weight USER_1 = sqrt(4) = 2, weight USER_2 = sqrt(1) = 1
+        // 	- USER_1 reward = 100.000 * 2 / 3 = 66.666 -> each nft = 16.666
+        // 	- USER_2 reward = 100.000 * 1 / 3 = 33.333
+        #[test]
+        fn wrong_operation_stake_duplicate_nfts() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1 twice in one call
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![
+                        NftStake {
+                            token_id: "1".to_string(),
+                            lockup_term: 299,
+                        },
+                        NftStake {
+                            token_id: "1".to_string(),
+                            lockup_term: 299,
+                        },
+                    ],
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response
+                    .unwrap_err()
+                    .downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::DuplicateTokenId { token_id }) if token_id == "1"
+            ));
+
+            // USER_1 stake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // nft 1 is already staked
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response
+                    .unwrap_err()
+                    .downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::AlreadyExist {})
+            ));
+
+            // increase 1 block, power is counted from next block
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.total_nft_staked, 1);
+
+            let total_power: TotalPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPowerAtHeight { height: None },
+                )
+                .unwrap();
+
+            assert_eq!(total_power.power, Uint128::from(1u128));
+
+            // USER_1 unstake nft 1 after its lockup term
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(300),
+                height: app.block_info().height + 300,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // no power or count is left behind
+            // increase 1 block, power is counted from next block
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.total_nft_staked, 0);
+
+            let total_power: TotalPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPowerAtHeight { height: None },
+                )
+                .unwrap();
+
+            assert_eq!(total_power.power, Uint128::from(0u128));
+        }
+
+        #[test]
+        fn proper_operation_square_root_distribution() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id = 1..4 to USER_1, token_id 5 to USER_2
+            for id in 1..6 {
+                let owner = if id < 5 { USER_1 } else { USER_2 };
+
+                // mint nft
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg.clone());
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+            }
+
+            // Approve all nft of USER_1 and USER_2 to campaign contract
+            for owner in [USER_1, USER_2] {
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign_msg = crate::msg::ExecuteMsg::CreateCampaign {
+                create_campaign: CreateCampaign {
+                    owner: ADMIN.to_string(),
+                    campaign_name: "campaign name".to_string(),
+                    campaign_image: "campaign name".to_string(),
+                    campaign_description: "campaign name".to_string(),
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    limit_per_staker: 5,
+                    reward_token_info: AssetToken {
+                        info: TokenInfo::Token {
+                            contract_addr: token_contract.to_string(),
+                        },
+                        amount: Uint128::zero(),
+                    },
+                    allowed_collection: collection_contract.clone(),
+                    lockup_term: vec![LockupTerm {
+                        value: 50,
+                        percent: Uint128::new(100u128),
+                    }],
+                    distribution_mode: Some(DistributionMode::SquareRoot),
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
+                },
+                salt: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &create_campaign_msg,
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add reward token
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 20 second to make active campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 20,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft token_id 1..4, USER_2 stake nft token_id 5
+            for (owner, token_ids) in [(USER_1, vec![1, 2, 3, 4]), (USER_2, vec![5])] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: token_ids
+                        .iter()
+                        .map(|id| NftStake {
+                            token_id: id.to_string(),
+                            lockup_term: 50,
+                        })
+                        .collect(),
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // increase 10 second
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 10,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // each nft of USER_1 has 66.666 / 4 = 16.666 reward
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.pending_reward, Uint128::from(16666u128));
+
+            // USER_1 reward_debt = 4 * 16.6665, rounded once over the nfts of staker = 66.666
+            let staked: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staked.reward_debt, Uint128::from(66666u128));
+
+            // USER_2 reward_debt = 33.333
+            let staked: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staked.reward_debt, Uint128::from(33333u128));
+
+            // USER_2 claim reward
+            let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
+                amount: Uint128::from(33333u128),
+                receipt_ids: None,
+            };
+
+            // Execute claim reward
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &claim_reward_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // query balance of USER_2 in cw20 base token contract
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_2.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(33333u128));
+        }
+
+        // -------------- loyalty multiplier ------------------
+        // - ADMIN create campaign with loyalty_steps = [10s -> x2]
+        // - add 1000.000 reward token, end time 100s -> reward_per_second = 10.000 token
+        // - one lockup_term = 20s, percent = 100%
+        // - USER_1 stake nft 1 at s0, USER_2 stake nft 2 at s5
+        // 	- s0 -> s5: nft 1 = 50.000
+        // 	- s5 -> s10: nft 1 = 50.000 + 25.000 = 75.000, nft 2 = 25.000
+        // 	- s10 -> s15: nft 1 has x2 -> nft 1 = 75.000 + 33.333, nft 2 = 25.000 + 16.666
+        // 	- s15 -> s20: nft 2 has x2 -> nft 1 = 108.333 + 25.000 = 133.333, nft 2 = 41.666 + 25.000
+        // 	- s20 -> s25: nft 1 stake timeout -> nft 2 = 66.666 + 50.000 = 116.666, nft 2 stake timeout
+        // - USER_1 renew nft 1 at s25, loyalty is kept
+        // - USER_2 unstake and stake nft 2 again at s25, loyalty is reset
+        // 	- s25 -> s35: nft 1 = 133.333 + 66.666, nft 2 = 33.333
+        #[test]
+        fn proper_operation_loyalty_multiplier() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2
+            for (id, owner) in [(1, USER_1), (2, USER_2)] {
+                // mint nft
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg.clone());
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // loyalty step must increase staked_time
+            let mut create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 110,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 20,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: Some(vec![
+                    LoyaltyStep {
+                        staked_time: 10,
+                        multiplier: Uint128::new(200u128),
+                    },
+                    LoyaltyStep {
+                        staked_time: 10,
+                        multiplier: Uint128::new(300u128),
+                    },
+                ]),
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_err());
+
+            // create campaign contract by factory contract
+            create_campaign.loyalty_steps = Some(vec![LoyaltyStep {
+                staked_time: 10,
+                multiplier: Uint128::new(200u128),
+            }]);
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add reward token
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 20 second to make active campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 20,
+                chain_id: app.block_info().chain_id,
+            });
+            let start_time_1 = app.block_info().time.seconds();
+
+            // USER_1 stake nft token_id 1 at s0, USER_2 stake nft token_id 2 at s5
+            for (id, owner) in [(1, USER_1), (2, USER_2)] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term: 20,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                // increase 5 second
+                app.set_block(BlockInfo {
+                    time: app.block_info().time.plus_seconds(5),
+                    height: app.block_info().height + 5,
+                    chain_id: app.block_info().chain_id,
+                });
+            }
+
+            // increase 5 second -> s15
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(5),
+                height: app.block_info().height + 5,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // nft 1 has x2 weight since s10
+            for (id, pending_reward) in [(1, 108333u128), (2, 41666u128)] {
+                let nft_info: NftInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: id.to_string(),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(nft_info.pending_reward, Uint128::from(pending_reward));
+            }
+
+            // renew nft 1 msg
+            let renew_nft_msg = CampaignExecuteMsg::RenewNft {
+                token_id: "1".to_string(),
+                lockup_term: 20,
+            };
+
+            // Execute renew nft
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &renew_nft_msg,
+                &[],
+            );
+
+            // err with nft is still in lockup term
+            assert!(response.is_err());
+
+            // increase 10 second -> s25
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 10,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Execute renew nft
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &renew_nft_msg,
+                &[],
+            );
+
+            // err with sender is not owner of nft
+            assert!(response.is_err());
+
+            // Execute renew nft
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &renew_nft_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // nft 1 start new lockup term, start_time is kept
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                nft_info,
+                NftInfo {
+                    token_id: "1".to_string(),
+                    owner: Addr::unchecked(USER_1.to_string()),
+                    pending_reward: Uint128::from(133333u128),
+                    lockup_term: LockupTerm {
+                        value: 20,
+                        percent: Uint128::from(100u128)
+                    },
+                    is_end_reward: false,
+                    start_time: start_time_1,
+                    end_time: start_time_1 + 45
+                }
+            );
+
+            // USER_2 un stake nft 2
+            let un_stake_nft_msg = CampaignExecuteMsg::UnStakeNft {
+                token_id: "2".to_string(),
+            };
+
+            // Execute un stake nft
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &un_stake_nft_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_2 stake nft 2 again, loyalty is reset
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "2".to_string(),
+                    lockup_term: 20,
+                }],
+            };
+
+            // Execute stake nft to campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &stake_nft_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 10 second -> s35
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(10),
+                height: app.block_info().height + 10,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // nft 1 has x2 weight, nft 2 has x1 weight
+            for (id, pending_reward) in [(1, 199999u128), (2, 33333u128)] {
+                let nft_info: NftInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: id.to_string(),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(nft_info.pending_reward, Uint128::from(pending_reward));
+            }
+
+            // USER_2 reward_debt = 116.666 (nft 2 un staked) + 33.333
+            let staked: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staked.reward_debt, Uint128::from(149999u128));
+        }
+
+        #[test]
+        fn proper_operation_emission_schedule() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1
+            let mint_nft_msg = Cw721MintMsg {
+                token_id: "1".to_string(),
+                owner: USER_1.to_string(),
+                token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
+                extension: Some(Metadata {
+                    description: Some("Spaceship with Warp Drive".into()),
+                    name: Some("Starship USS Enterprise".to_string()),
+                    ..Metadata::default()
+                }),
+            };
+
+            let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+            let response_mint_nft = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(collection_contract.clone()),
+                &exec_msg,
+                &[],
+            );
+
+            assert!(response_mint_nft.is_ok());
+
+            // Approve all nft of USER_1 to campaign contract
+            let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                operator: "contract3".to_string(),
+                expires: None,
+            };
+
+            // Execute approve nft
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(collection_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+            let start_time = current_block_time + 10;
+
+            // emission schedule must start at start_time of campaign
+            let mut create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time,
+                end_time: start_time + 100,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 50,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: Some(vec![
+                    EmissionPhase {
+                        start_time: start_time + 10,
+                        weight: Uint128::new(3u128),
+                    },
+                    EmissionPhase {
+                        start_time: start_time + 50,
+                        weight: Uint128::new(1u128),
+                    },
+                ]),
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_err());
+
+            // create campaign contract by factory contract
+            // first 50s emit 3 times more reward than last 50s
+            create_campaign.emission_schedule = Some(vec![
+                EmissionPhase {
+                    start_time,
+                    weight: Uint128::new(3u128),
+                },
+                EmissionPhase {
+                    start_time: start_time + 50,
+                    weight: Uint128::new(1u128),
+                },
+            ]);
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add reward token
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 30 second -> s20 of campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(30),
+                height: app.block_info().height + 30,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // reward_per_second = 1000.000 * 3 / (3 * 50 + 1 * 50) = 15.000 then 5.000 token
+            let emission_schedule: EmissionScheduleResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::EmissionSchedule {})
+                .unwrap();
+
+            assert_eq!(
+                emission_schedule,
+                EmissionScheduleResult {
+                    current_reward_per_second: Uint128::from(15000u128),
+                    phases: vec![
+                        EmissionPhaseResult {
+                            start_time,
+                            end_time: start_time + 50,
+                            reward_per_second: Uint128::from(15000u128),
+                        },
+                        EmissionPhaseResult {
+                            start_time: start_time + 50,
+                            end_time: start_time + 100,
+                            reward_per_second: Uint128::from(5000u128),
+                        },
+                    ],
+                }
+            );
+
+            // USER_1 stake nft token_id 1
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 50,
+                }],
+            };
+
+            // Execute stake nft to campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &stake_nft_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 40 second -> s60 of campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(40),
+                height: app.block_info().height + 40,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // pending reward = 30s * 15.000 + 10s * 5.000 = 500.000 token
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.pending_reward, Uint128::from(500000u128));
+
+            // only last phase is left
+            let emission_schedule: EmissionScheduleResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::EmissionSchedule {})
+                .unwrap();
+
+            assert_eq!(
+                emission_schedule.current_reward_per_second,
+                Uint128::from(5000u128)
+            );
+            assert_eq!(emission_schedule.phases.len(), 1);
+
+            // USER_1 claim reward
+            let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
+                amount: Uint128::from(500000u128),
+                receipt_ids: None,
+            };
+
+            // Execute claim reward
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &claim_reward_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get balance of USER_1
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(500000u128));
+        }
+
+        #[test]
+        fn proper_operation_unallocated_reward() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 2000 tokens to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT * 2),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // contract3 keep unallocated reward, contract4 roll it into other lockup terms
+            for rollover_unallocated in [false, true] {
+                let create_campaign = CreateCampaign {
+                    owner: ADMIN.to_string(),
+                    campaign_name: "campaign name".to_string(),
+                    campaign_image: "campaign name".to_string(),
+                    campaign_description: "campaign name".to_string(),
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 110,
+                    limit_per_staker: 2,
+                    reward_token_info: AssetToken {
+                        info: TokenInfo::Token {
+                            contract_addr: token_contract.to_string(),
+                        },
+                        amount: Uint128::zero(),
+                    },
+                    allowed_collection: collection_contract.clone(),
+                    lockup_term: vec![
+                        LockupTerm {
+                            value: 10,
+                            percent: Uint128::new(50u128),
+                        },
+                        LockupTerm {
+                            value: 20,
+                            percent: Uint128::new(50u128),
+                        },
+                    ],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: Some(rollover_unallocated),
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
+                };
+
+                // Execute create campaign
+                let response_create_campaign = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(factory_contract.clone()),
+                    &crate::msg::ExecuteMsg::CreateCampaign {
+                        create_campaign,
+                        salt: None,
+                    },
+                    &[],
+                );
+
+                assert!(response_create_campaign.is_ok());
+            }
+
+            // mint nft token_id 1, 2 to USER_1
+            for id in [1, 2] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: USER_1.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+            }
+
+            for campaign_contract in ["contract3", "contract4"] {
+                // Approve all nft of USER_1 to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: campaign_contract.to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(USER_1.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+
+                // Approve cw20 token to campaign contract
+                let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: campaign_contract.to_string(),
+                    amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+                    expires: None,
+                };
+
+                // Execute approve
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(token_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+
+                // add reward token, reward_per_second = 10.000 token
+                let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                    amount: Uint128::from(MOCK_1000_TOKEN_AMOUNT),
+                };
+
+                // Execute add reward balance
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(campaign_contract),
+                    &add_reward_balance_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // increase 20 second -> s10 of campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 20,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1 to contract3, nft 2 to contract4 in lockup term 10
+            for (id, campaign_contract) in [(1, "contract3"), (2, "contract4")] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term: 10,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(USER_1.to_string()),
+                    Addr::unchecked(campaign_contract),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // increase 20 second -> s30 of campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(20),
+                height: app.block_info().height + 20,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // contract3: nft 1 get share of term 10 in s10 -> s20, the rest is unallocated
+            // contract4: nft 2 get share of both terms in s10 -> s20
+            for (id, campaign_contract, pending_reward, unallocated_reward) in [
+                (1, "contract3", 50000u128, [100000u128, 150000u128]),
+                (2, "contract4", 100000u128, [100000u128, 100000u128]),
+            ] {
+                let nft_info: NftInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        campaign_contract,
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: id.to_string(),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(nft_info.pending_reward, Uint128::from(pending_reward));
+
+                let unallocated: Vec<UnallocatedRewardResult> = app
+                    .wrap()
+                    .query_wasm_smart(campaign_contract, &CampaignQueryMsg::UnallocatedReward {})
+                    .unwrap();
+
+                assert_eq!(
+                    unallocated,
+                    vec![
+                        UnallocatedRewardResult {
+                            lockup_term: 10,
+                            amount: Uint128::from(unallocated_reward[0]),
+                        },
+                        UnallocatedRewardResult {
+                            lockup_term: 20,
+                            amount: Uint128::from(unallocated_reward[1]),
+                        },
+                    ]
+                );
+            }
+
+            // Execute reclaim unallocated reward
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ReclaimUnallocatedReward {},
+                &[],
+            );
+
+            // err with sender is not owner of campaign
+            assert!(response.is_err());
+
+            // ADMIN reclaim unallocated reward of both campaigns
+            for campaign_contract in ["contract3", "contract4"] {
+                let response = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(campaign_contract),
+                    &CampaignExecuteMsg::ReclaimUnallocatedReward {},
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // get balance of ADMIN
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // 250.000 + 200.000 token
+            assert_eq!(balance.balance, Uint128::from(450000u128));
+
+            // nothing left to reclaim
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ReclaimUnallocatedReward {},
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            let unallocated: Vec<UnallocatedRewardResult> = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::UnallocatedReward {})
+                .unwrap();
+
+            assert!(unallocated.iter().all(|reward| reward.amount.is_zero()));
+        }
+
+        #[test]
+        fn proper_operation_reward_dust() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // each user stake 1 nft
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term: 299,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // increase 299 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(299),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // reward in s1 -> s300 is 996.66 unit, 332.22 unit per nft
+            // reward in s0 -> s1 is 3.33 unit, 3 unit is unallocated
+            for id in [1, 2, 3] {
+                let nft_info: NftInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: id.to_string(),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(nft_info.pending_reward, Uint128::from(332u128));
+            }
+
+            // USER_1 claim reward
+            let claim_reward_msg = CampaignExecuteMsg::ClaimReward {
+                amount: Uint128::from(332u128),
+                receipt_ids: None,
+            };
+
+            // Execute claim reward
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &claim_reward_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // rounding remainder of nft reward stays with its staker, dust is the remainder
+            // of unallocated reward = 0.33 unit
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.reward_dust, Uint128::zero());
+
+            // Execute withdraw reward
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::WithdrawReward {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get balance of ADMIN
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            // unallocated reward and 1 unit of rounding dust are returned to owner
+            assert_eq!(balance.balance, Uint128::from(4u128));
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.reward_dust, Uint128::zero());
+        }
+
+        #[test]
+        fn proper_operation_lazy_pending_reward() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 900 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(900u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2
+            for (id, owner) in [(1, USER_1), (2, USER_2)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract, x2 multiplier after 100 seconds
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 250,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: Some(vec![LoyaltyStep {
+                    staked_time: 100,
+                    multiplier: Uint128::new(200u128),
+                }]),
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(900u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 900 unit of reward token, 3 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(900u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase `seconds`, then owner stakes nft
+            let stake_nft = |app: &mut App, seconds: u64, id: u64, owner: &str| {
+                app.set_block(BlockInfo {
+                    time: app.block_info().time.plus_seconds(seconds),
+                    height: app.block_info().height + seconds,
+                    chain_id: app.block_info().chain_id,
+                });
+                app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: id.to_string(),
+                            lockup_term: 250,
+                        }],
+                    },
+                    &[],
+                )
+            };
+            let saved_nft = |app: &App, id: u64| -> NftInfo {
+                app.wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::Nft {
+                            token_id: id.to_string(),
+                        },
+                    )
+                    .unwrap()
+            };
+            let pending_nft = |app: &App, id: u64| -> NftInfo {
+                app.wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: id.to_string(),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap()
+            };
+
+            // USER_1 stakes at s1, USER_2 stakes at s151 after loyalty step of nft 1 at s101
+            assert!(stake_nft(&mut app, 11, 1, USER_1).is_ok());
+            assert!(stake_nft(&mut app, 150, 2, USER_2).is_ok());
+
+            // stake of USER_2 updates reward of campaign, nft 1 is only saved at its loyalty
+            // step with reward of s1 -> s101
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+            assert_eq!(campaign_info.time_calc_nft, current_block_time + 161);
+            assert_eq!(saved_nft(&app, 1).pending_reward, Uint128::from(300u128));
+
+            // s1 -> s101: 300 unit, s101 -> s151: 150 unit, both to nft 1
+            assert_eq!(pending_nft(&app, 1).pending_reward, Uint128::from(450u128));
+
+            // increase 60 second, nft 1 (x2) and nft 2 (x1) share 180 unit
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(60),
+                height: app.block_info().height + 60,
+                chain_id: app.block_info().chain_id,
+            });
+
+            assert_eq!(pending_nft(&app, 1).pending_reward, Uint128::from(570u128));
+            assert_eq!(pending_nft(&app, 2).pending_reward, Uint128::from(60u128));
+
+            // increase 89 second to end campaign without any update
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(89),
+                height: app.block_info().height + 89,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // s211 -> s251: 120 unit shared 2:1, nft 1 ends at s251, s251 -> s300 to nft 2
+            let nft_1 = pending_nft(&app, 1);
+            assert_eq!(nft_1.pending_reward, Uint128::from(650u128));
+            assert!(nft_1.is_end_reward);
+            let nft_2 = pending_nft(&app, 2);
+            assert_eq!(nft_2.pending_reward, Uint128::from(247u128));
+            assert!(nft_2.is_end_reward);
+
+            // pending events are processed when USER_1 claims, nft 2 is only saved at its
+            // loyalty step with reward of s151 -> s251
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(650u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+            assert!(response.is_ok());
+
+            assert_eq!(saved_nft(&app, 1).pending_reward, Uint128::zero());
+            assert!(saved_nft(&app, 1).is_end_reward);
+            assert_eq!(saved_nft(&app, 2).pending_reward, Uint128::from(100u128));
+            assert!(!saved_nft(&app, 2).is_end_reward);
+            assert_eq!(pending_nft(&app, 2).pending_reward, Uint128::from(247u128));
+
+            // 3 unit of s0 -> s1 is unallocated
+            let total_pending_reward: Uint128 = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward { at_time: None },
+                )
+                .unwrap();
+            assert_eq!(total_pending_reward, Uint128::from(247u128));
+        }
+
+        #[test]
+        fn proper_operation_stakers() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // each user stake 1 nft
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term: 299,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // increase 299 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(299),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 unstake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 has no nft staked but still counted in all-time stakers
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.total_nft_staked, 2);
+            assert_eq!(campaign_info.current_stakers, 2);
+            assert_eq!(campaign_info.total_stakers, 3);
+
+            // query first page of stakers
+            let stakers: Vec<StakerInfoResult> = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::Stakers {
+                        start_after: None,
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                stakers,
+                vec![
+                    StakerInfoResult {
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        nft_count: 0,
+                        reward_debt: Uint128::from(332u128),
+                        reward_claimed: Uint128::zero(),
+                    },
+                    StakerInfoResult {
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        nft_count: 1,
+                        reward_debt: Uint128::from(332u128),
+                        reward_claimed: Uint128::zero(),
+                    },
+                ]
+            );
+
+            // query next page of stakers
+            let stakers: Vec<StakerInfoResult> = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::Stakers {
+                        start_after: Some(USER_2.to_string()),
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                stakers,
+                vec![StakerInfoResult {
+                    owner: Addr::unchecked(USER_3.to_string()),
+                    nft_count: 1,
+                    reward_debt: Uint128::from(332u128),
+                    reward_claimed: Uint128::zero(),
+                }]
+            );
+        }
+
+        #[test]
+        fn proper_operation_staker_counters() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 and 2 to USER_1, token_id 3 to USER_2
+            for (id, owner) in [(1, USER_1), (2, USER_1), (3, USER_2)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 10,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1 and 2, USER_2 stake nft 3
+            for (owner, token_ids) in [(USER_1, vec!["1", "2"]), (USER_2, vec!["3"])] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: token_ids
+                        .into_iter()
+                        .map(|token_id| NftStake {
+                            token_id: token_id.to_string(),
+                            lockup_term: 10,
+                        })
+                        .collect(),
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            let query_counters = |app: &App| -> (u64, u64, u64, u64) {
+                let campaign_info: CampaignInfoResult = app
+                    .wrap()
+                    .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                    .unwrap();
+                let stakers: Vec<StakerInfoResult> = app
+                    .wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::Stakers {
+                            start_after: None,
+                            limit: Some(1),
+                        },
+                    )
+                    .unwrap();
+
+                (
+                    campaign_info.total_nft_staked,
+                    campaign_info.current_stakers,
+                    campaign_info.total_stakers,
+                    stakers[0].nft_count,
+                )
+            };
+
+            // (total nft staked, current stakers, total stakers, nft count of USER_1)
+            assert_eq!(query_counters(&app), (3, 2, 2, 2));
+
+            // increase 11 second to end lockup term
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 unstake nft 1, still a current staker
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+            assert_eq!(query_counters(&app), (2, 2, 2, 1));
+
+            // USER_1 unstake nft 2, no longer a current staker
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "2".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+            assert_eq!(query_counters(&app), (1, 1, 2, 0));
+
+            // USER_1 re-stake nft 1, counted again as current staker but not as new staker
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 10,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+            assert_eq!(query_counters(&app), (2, 2, 2, 1));
+        }
+
+        #[test]
+        fn proper_operation_simulate_stake() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // simulate staking nft 1 and 2 at s1 until end of lockup term s300
+            let simulate_stake_msg = CampaignQueryMsg::SimulateStake {
+                nfts: vec![
+                    NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    },
+                    NftStake {
+                        token_id: "2".to_string(),
+                        lockup_term: 299,
+                    },
+                ],
+                staker: None,
+                at_time: None,
+            };
+
+            let simulate_result: SimulateStakeResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &simulate_stake_msg)
+                .unwrap();
+
+            // reward in s1 -> s300 is 996.66 unit, 498.33 unit per nft
+            // annual reward = 498 (reward in lockup term) * 31536000 / 299 (lockup term)
+            let stake_time = app.block_info().time.seconds();
+            assert_eq!(
+                simulate_result,
+                SimulateStakeResult {
+                    at_time: stake_time + 299,
+                    nfts: vec![
+                        SimulatedNftResult {
+                            token_id: "1".to_string(),
+                            lockup_term: 299,
+                            start_time: stake_time,
+                            end_time: stake_time + 299,
+                            projected_reward: Uint128::from(498u128),
+                            annual_reward: Uint128::from(52524842u128),
+                        },
+                        SimulatedNftResult {
+                            token_id: "2".to_string(),
+                            lockup_term: 299,
+                            start_time: stake_time,
+                            end_time: stake_time + 299,
+                            projected_reward: Uint128::from(498u128),
+                            annual_reward: Uint128::from(52524842u128),
+                        },
+                    ],
+                    total_projected_reward: Uint128::from(996u128),
+                }
+            );
+
+            // simulate staking nft 1 and 2 at s1 until s100, reward is 330 unit
+            let simulate_result: SimulateStakeResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::SimulateStake {
+                        nfts: vec![
+                            NftStake {
+                                token_id: "1".to_string(),
+                                lockup_term: 299,
+                            },
+                            NftStake {
+                                token_id: "2".to_string(),
+                                lockup_term: 299,
+                            },
+                        ],
+                        staker: None,
+                        at_time: Some(stake_time + 99),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(simulate_result.at_time, stake_time + 99);
+            assert_eq!(
+                simulate_result.total_projected_reward,
+                Uint128::from(330u128)
+            );
+
+            // simulate with invalid lockup term
+            let response: Result<SimulateStakeResult, _> = app.wrap().query_wasm_smart(
+                "contract3",
+                &CampaignQueryMsg::SimulateStake {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 10,
+                    }],
+                    staker: None,
+                    at_time: None,
+                },
+            );
+
+            assert!(response.is_err());
+
+            // USER_1 and USER_2 stake nft 1 and 2 as simulated
+            for (id, owner) in [(1, USER_1), (2, USER_2)] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term: 299,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // pending reward of nft 1 at end of lockup term is the projected reward
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                        at_time: Some(stake_time + 299),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.pending_reward, Uint128::from(498u128));
+            assert!(nft_info.is_end_reward);
+
+            // reward debt of USER_1 at s100 is 165 unit
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: Some(stake_time + 99),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staked_info.reward_debt, Uint128::from(165u128));
+
+            // total pending reward at end of campaign
+            let total_pending_reward: Uint128 = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward {
+                        at_time: Some(stake_time + 299),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(total_pending_reward, Uint128::from(996u128));
+
+            // nfts at end of campaign
+            let nfts: Vec<NftInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::Nfts {
+                        lockup_term: None,
+                        start_after: None,
+                        limit: None,
+                        at_time: Some(stake_time + 299),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nfts.len(), 2);
+            assert!(nfts
+                .iter()
+                .all(|nft| nft.pending_reward == Uint128::from(498u128)));
+
+            // query pending reward in the past
+            let past_queries = [
+                CampaignQueryMsg::TotalPendingReward {
+                    at_time: Some(stake_time - 1),
+                },
+                CampaignQueryMsg::NftInfo {
+                    token_id: "1".to_string(),
+                    at_time: Some(stake_time - 1),
+                },
+                CampaignQueryMsg::NftStaked {
+                    owner: Addr::unchecked(USER_1.to_string()),
+                    start_after: None,
+                    limit: None,
+                    at_time: Some(stake_time - 1),
+                },
+                CampaignQueryMsg::Nfts {
+                    lockup_term: None,
+                    start_after: None,
+                    limit: None,
+                    at_time: Some(stake_time - 1),
+                },
+            ];
+            for query in past_queries {
+                let response: Result<Empty, _> = app.wrap().query_wasm_smart("contract3", &query);
+
+                assert!(response
+                    .unwrap_err()
+                    .to_string()
+                    .contains(&CampaignContractError::InvalidQueryTime {}.to_string()));
+            }
+
+            // increase 299 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(299),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // pending reward is the same as projected reward
+            for id in [1, 2] {
+                let nft_info: NftInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: id.to_string(),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(nft_info.pending_reward, Uint128::from(498u128));
+            }
+        }
+
+        #[test]
+        fn proper_operation_simulate_stake_by_staker() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 and 2 to USER_1, token_id 3 to USER_2
+            for (id, owner) in [(1, USER_1), (2, USER_1), (3, USER_2)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: Some(DistributionMode::SquareRoot),
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1, USER_2 stake nft 3
+            for (id, owner) in [(1, USER_1), (3, USER_2)] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term: 299,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            let simulate_stake = |app: &App, staker: Option<String>| -> SimulateStakeResult {
+                app.wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::SimulateStake {
+                            nfts: vec![NftStake {
+                                token_id: "2".to_string(),
+                                lockup_term: 299,
+                            }],
+                            staker,
+                            at_time: None,
+                        },
+                    )
+                    .unwrap()
+            };
+
+            // nft 2 of USER_1 shares the sqrt weight of USER_1 with nft 1,
+            // so it earns less than a nft of a new staker
+            let by_new_staker = simulate_stake(&app, None);
+            let by_user_1 = simulate_stake(&app, Some(USER_1.to_string()));
+
+            assert!(by_user_1.total_projected_reward < by_new_staker.total_projected_reward);
+            assert!(by_user_1.nfts[0].annual_reward < by_new_staker.nfts[0].annual_reward);
+
+            // simulate with invalid staker
+            let response: Result<SimulateStakeResult, _> = app.wrap().query_wasm_smart(
+                "contract3",
+                &CampaignQueryMsg::SimulateStake {
+                    nfts: vec![NftStake {
+                        token_id: "2".to_string(),
+                        lockup_term: 299,
+                    }],
+                    staker: Some("INVALID_STAKER".to_string()),
+                    at_time: None,
+                },
+            );
+
+            assert!(response.is_err());
+
+            // USER_1 stake nft 2 as simulated
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "2".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // pending reward of nft 2 at end of lockup term is the projected reward of USER_1
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "2".to_string(),
+                        at_time: Some(by_user_1.at_time),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.pending_reward, by_user_1.total_projected_reward);
+        }
+
+        #[test]
+        fn proper_operation_solvency() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 299,
+                }],
+            };
+
+            // Execute stake nft to campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &stake_nft_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 99 second -> s100
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(99),
+                height: app.block_info().height + 99,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // Mint 50 unit of token to ADMIN and send to campaign without AddRewardToken
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(50u128),
+            };
+
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let transfer_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Transfer {
+                recipient: "contract3".to_string(),
+                amount: Uint128::from(50u128),
+            };
+
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &transfer_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // pending reward of nft 1 in s1 -> s100 is 330 unit
+            let solvency: SolvencyResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::Solvency {})
+                .unwrap();
+
+            assert_eq!(
+                solvency,
+                SolvencyResult {
+                    total_liabilities: Uint128::from(330u128),
+                    recorded_balance: Uint128::from(1000u128),
+                    actual_balance: Uint128::from(1050u128),
+                    surplus: Uint128::from(50u128),
+                    deficit: Uint128::zero(),
+                    is_solvent: true,
+                }
+            );
+
+            // only owner can reconcile
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::Reconcile {},
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // ADMIN reconcile surplus
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::Reconcile {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // get balance of ADMIN
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(50u128));
+
+            // recorded balance is kept for stakers
+            let solvency: SolvencyResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::Solvency {})
+                .unwrap();
+
+            assert_eq!(solvency.recorded_balance, Uint128::from(1000u128));
+            assert_eq!(solvency.actual_balance, Uint128::from(1000u128));
+            assert_eq!(solvency.surplus, Uint128::zero());
+
+            // no surplus to reconcile
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::Reconcile {},
+                &[],
+            );
+
+            assert!(response.is_err());
+        }
+
+        #[test]
+        fn proper_operation_receipt() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: Some(contracts[2].contract_code_id),
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // receipt collection is instantiated by campaign
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(
+                campaign_info.receipt_collection,
+                Some(Addr::unchecked("contract4"))
+            );
+
+            // store and instantiate hook contract -> contract5
+            let hook_code_id = app.store_code(hook_contract_template());
+            let hook_contract = app
+                .instantiate_contract(
+                    hook_code_id,
+                    Addr::unchecked(ADMIN.to_string()),
+                    &Empty {},
+                    &[],
+                    "hook contract",
+                    None,
+                )
+                .unwrap();
+
+            // ADMIN add hook
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddHook {
+                    addr: hook_contract.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 stake nft 1
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 299,
+                }],
+            };
+
+            // Execute stake nft to campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &stake_nft_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // receipt 1 is minted to USER_1
+            let receipt: cw721::OwnerOfResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract4",
+                    &cw721::Cw721QueryMsg::OwnerOf {
+                        token_id: "1".to_string(),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(receipt.owner, USER_1.to_string());
+
+            // increase 149 second -> s150
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(149),
+                height: app.block_info().height + 149,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 claim reward of nft 1 before selling receipt 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(200u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 sell receipt 1 to USER_2
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract4"),
+                &Cw721ExecuteMsg::TransferNft {
+                    recipient: USER_2.to_string(),
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 50 second -> s200
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 50,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_2 cannot claim more than 100 receipts at once
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(1u128),
+                    receipt_ids: Some((0..101).map(|id| id.to_string()).collect()),
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response
+                    .unwrap_err()
+                    .downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::TooManyTokenIds {})
+            ));
+
+            // reward in s150 -> s200 is 166.66 unit and follows receipt 1, USER_1 cannot claim it
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(297u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response
+                    .unwrap_err()
+                    .downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::InsufficientBalance {})
+            ));
+
+            // USER_1 claim the rest of reward settled before sale of receipt 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(296u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // position of nft 1 is moved to USER_2 with its unclaimed reward
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.owner, Addr::unchecked(USER_2.to_string()));
+            assert_eq!(nft_info.pending_reward, Uint128::from(166u128));
+
+            // USER_2 claim reward of nft 1 since sale of receipt 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(166u128),
+                    receipt_ids: Some(vec!["1".to_string()]),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // nft with receipt cannot be unstaked directly
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // increase 100 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100),
+                height: app.block_info().height + 100,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_2 unstake nft 1 by sending receipt 1 to campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract4"),
+                &Cw721ExecuteMsg::SendNft {
+                    contract: "contract3".to_string(),
+                    token_id: "1".to_string(),
+                    msg: Default::default(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // nft 1 is transferred to USER_2
+            let owner: cw721::OwnerOfResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    collection_contract.clone(),
+                    &cw721::Cw721QueryMsg::OwnerOf {
+                        token_id: "1".to_string(),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(owner.owner, USER_2.to_string());
+
+            // receipt 1 is burned
+            let response: Result<cw721::OwnerOfResponse, _> = app.wrap().query_wasm_smart(
+                "contract4",
+                &cw721::Cw721QueryMsg::OwnerOf {
+                    token_id: "1".to_string(),
+                    include_expired: None,
+                },
+            );
+
+            assert!(response.is_err());
+
+            // reward in s200 -> s300 is 333.33 unit
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                staked_info,
+                StakedInfoResult {
+                    nfts: vec![],
+                    reward_debt: Uint128::from(333u128),
+                    reward_claimed: Uint128::from(166u128),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::from(166u128),
+                }
+            );
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.total_nft_staked, 0);
+            assert_eq!(campaign_info.current_stakers, 0);
+            assert_eq!(campaign_info.total_stakers, 2);
+
+            // hook follows position of nft 1 from USER_1 to USER_2
+            let hook_msgs: Vec<StakeChangedHookMsg> = app
+                .wrap()
+                .query_wasm_smart(hook_contract.clone(), &Empty {})
+                .unwrap();
+
+            assert_eq!(
+                hook_msgs,
+                vec![
+                    StakeChangedHookMsg::Stake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                    },
+                    StakeChangedHookMsg::Claim {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        amount: Uint128::from(200u128),
+                    },
+                    StakeChangedHookMsg::Unstake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                        reward: Uint128::from(0u128),
+                    },
+                    StakeChangedHookMsg::Stake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                    },
+                    StakeChangedHookMsg::Claim {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        amount: Uint128::from(296u128),
+                    },
+                    StakeChangedHookMsg::Claim {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        amount: Uint128::from(166u128),
+                    },
+                    StakeChangedHookMsg::Unstake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                        reward: Uint128::from(333u128),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn proper_operation_mint_reward() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: Some(RewardMode::Mint {
+                    reward_per_second: Uint128::from(2u128),
+                    mint_cap: Some(Uint128::from(500u128)),
+                }),
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // campaign becomes minter of reward token
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::UpdateMinter {
+                    new_minter: Some("contract3".to_string()),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // reward of mint mode can not be added
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddRewardToken {
+                    amount: Uint128::from(1000u128),
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // total reward = 2 (reward_per_second) * 300s
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.reward_per_second, Uint128::from(2u128));
+            assert_eq!(campaign_info.total_reward, Uint128::from(600u128));
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1 without reward added
+            let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                nfts: vec![NftStake {
+                    token_id: "1".to_string(),
+                    lockup_term: 299,
+                }],
+            };
+
+            // Execute stake nft to campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &stake_nft_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 249 second -> s250
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(249),
+                height: app.block_info().height + 249,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // emission reaches mint cap at s250, reward in s0 -> s1 is unallocated
+            // reward in s1 -> s250 is 500 - 2 = 498 unit
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.pending_reward, Uint128::from(498u128));
+
+            // increase 50 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 50,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // no reward is emitted after mint cap is reached
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.pending_reward, Uint128::from(498u128));
+
+            // reward above mint cap can not be claimed
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(598u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(matches!(
+                response
+                    .unwrap_err()
+                    .downcast_ref::<CampaignContractError>(),
+                Some(CampaignContractError::InsufficientBalance {})
+            ));
+
+            // claim all reward
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(498u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // reward is minted to USER_1
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(498u128));
+
+            // all reward emitted under mint cap is claimed
+            let total_pending_reward: Uint128 = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPendingReward { at_time: None },
+                )
+                .unwrap();
+
+            assert_eq!(total_pending_reward, Uint128::zero());
+
+            // withdraw reward is no-op
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::WithdrawReward {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(1000u128));
+        }
+
+        #[test]
+        fn proper_operation_soft_staking() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: Some(true),
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // store and instantiate hook contract -> contract4
+            let hook_code_id = app.store_code(hook_contract_template());
+            let hook_contract = app
+                .instantiate_contract(
+                    hook_code_id,
+                    Addr::unchecked(ADMIN.to_string()),
+                    &Empty {},
+                    &[],
+                    "hook contract",
+                    None,
+                )
+                .unwrap();
+
+            // ADMIN add hook
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddHook {
+                    addr: hook_contract.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 stake nft 1 and USER_2 stake nft 2
+            for (id, owner) in [(1, USER_1), (2, USER_2)] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term: 299,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // nft 1 stays in wallet of USER_1
+            let owner: cw721::OwnerOfResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    collection_contract.clone(),
+                    &cw721::Cw721QueryMsg::OwnerOf {
+                        token_id: "1".to_string(),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(owner.owner, USER_1.to_string());
+
+            // nft 1 can not be staked twice
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // increase 99 second -> s100
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(99),
+                height: app.block_info().height + 99,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 transfer nft 1 to USER_3
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(collection_contract.clone()),
+                &Cw721ExecuteMsg::TransferNft {
+                    recipient: USER_3.to_string(),
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 50 second -> s150
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 50,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // checkpoint finds nft 1 transferred
+            let response = app.execute_contract(
+                Addr::unchecked(USER_3.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::Checkpoint {
+                    start_after: None,
+                    limit: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // reward in s1 -> s150 is 496.66 unit, 248.33 unit per nft
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                staked_info,
+                StakedInfoResult {
+                    nfts: vec![],
+                    reward_debt: Uint128::from(248u128),
+                    reward_claimed: Uint128::zero(),
+                    protocol_fee_paid: Uint128::zero(),
+                    net_reward_claimed: Uint128::zero(),
+                }
+            );
+
+            // USER_3 stake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_3.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 150 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(150),
+                height: app.block_info().height + 150,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // reward in s150 -> s300 is 500 unit, 250 unit per nft
+            for (id, owner, pending_reward) in [(1, USER_3, 250u128), (2, USER_2, 498u128)] {
+                let nft_info: NftInfo = app
+                    .wrap()
+                    .query_wasm_smart(
+                        "contract3",
+                        &CampaignQueryMsg::NftInfo {
+                            token_id: id.to_string(),
+                            at_time: None,
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(nft_info.owner, Addr::unchecked(owner.to_string()));
+                assert_eq!(nft_info.pending_reward, Uint128::from(pending_reward));
+            }
+
+            // USER_2 unstake nft 2, nft is already in wallet
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "2".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_3 burn nft 1, burned nft is not owned by staker anymore
+            let response = app.execute_contract(
+                Addr::unchecked(USER_3.to_string()),
+                Addr::unchecked(collection_contract.clone()),
+                &Cw721ExecuteMsg::Burn {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // checkpoint releases burned nft 1, its reward is kept for USER_3
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::Checkpoint {
+                    start_after: None,
+                    limit: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_3.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staked_info.nfts, vec![]);
+            assert_eq!(staked_info.reward_debt, Uint128::from(250u128));
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.total_nft_staked, 0);
+            assert_eq!(campaign_info.current_stakers, 0);
+            assert_eq!(campaign_info.total_stakers, 3);
+
+            // hook is notified of nfts released by checkpoint
+            let hook_msgs: Vec<StakeChangedHookMsg> = app
+                .wrap()
+                .query_wasm_smart(hook_contract.clone(), &Empty {})
+                .unwrap();
+
+            assert_eq!(
+                hook_msgs,
+                vec![
+                    StakeChangedHookMsg::Stake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                    },
+                    StakeChangedHookMsg::Stake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "2".to_string(),
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                    },
+                    StakeChangedHookMsg::Unstake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                        reward: Uint128::from(248u128),
+                    },
+                    StakeChangedHookMsg::Stake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_3.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                    },
+                    StakeChangedHookMsg::Unstake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "2".to_string(),
+                        owner: Addr::unchecked(USER_2.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                        reward: Uint128::from(498u128),
+                    },
+                    StakeChangedHookMsg::Unstake {
+                        collection: Addr::unchecked(collection_contract.clone()),
+                        token_id: "1".to_string(),
+                        owner: Addr::unchecked(USER_3.to_string()),
+                        lockup_term: 299,
+                        amount: Uint128::one(),
+                        reward: Uint128::from(250u128),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn proper_operation_soft_staking_collection_failure() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // store and instantiate pausable collection contract -> contract3
+            let collection_code_id = app.store_code(pausable_collection_contract_template());
+            let collection_contract = app
+                .instantiate_contract(
+                    collection_code_id,
+                    Addr::unchecked(ADMIN.to_string()),
+                    &cw721_base::msg::InstantiateMsg {
+                        name: "LP Token".to_string(),
+                        symbol: "LPTT".to_string(),
+                        minter: ADMIN.to_string(),
+                    },
+                    &[],
+                    "pausable collection",
+                    None,
+                )
+                .unwrap();
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2
+            for (id, owner) in [(1, USER_1), (2, USER_2)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: None,
+                    extension: None,
+                };
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    collection_contract.clone(),
+                    &Cw721ExecuteMsg::Mint(mint_nft_msg),
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create soft staking campaign -> contract4
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.to_string(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: Some(true),
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: "contract4".to_string(),
+                    amount: Uint128::from(1000u128),
+                    expires: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract4"),
+                &CampaignExecuteMsg::AddRewardToken {
+                    amount: Uint128::from(1000u128),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1 and USER_2 stake nft 2
+            for (id, owner) in [(1, USER_1), (2, USER_2)] {
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract4"),
+                    &CampaignExecuteMsg::StakeNfts {
+                        nfts: vec![NftStake {
+                            token_id: id.to_string(),
+                            lockup_term: 299,
+                        }],
+                    },
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // increase 99 second -> s100
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(99),
+                height: app.block_info().height + 99,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 burn nft 1, OwnerOf of nft 1 fails while collection answers NumTokens
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                collection_contract.clone(),
+                &Cw721ExecuteMsg::Burn {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // checkpoint releases burned nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract4"),
+                &CampaignExecuteMsg::Checkpoint {
+                    start_after: None,
+                    limit: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // reward in s1 -> s100 is 330 unit, 165 unit per nft
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract4",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staked_info.nfts, vec![]);
+            assert_eq!(staked_info.reward_debt, Uint128::from(165u128));
+
+            // collection fails every query
+            let response = app.wasm_sudo(collection_contract.clone(), &true);
+
+            assert!(response.is_ok());
+
+            // checkpoint and claim fail instead of releasing nft 2
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract4"),
+                &CampaignExecuteMsg::Checkpoint {
+                    start_after: None,
+                    limit: None,
+                },
+                &[],
+            );
+
+            assert!(response
+                .unwrap_err()
+                .root_cause()
+                .to_string()
+                .contains("collection is paused"));
+
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked("contract4"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(1u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response
+                .unwrap_err()
+                .root_cause()
+                .to_string()
+                .contains("collection is paused"));
+
+            // collection answers queries again
+            let response = app.wasm_sudo(collection_contract.clone(), &false);
+
+            assert!(response.is_ok());
+
+            // checkpoint keeps nft 2 staked with USER_2
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract4"),
+                &CampaignExecuteMsg::Checkpoint {
+                    start_after: None,
+                    limit: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let nft_info: NftInfo = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract4",
+                    &CampaignQueryMsg::NftInfo {
+                        token_id: "2".to_string(),
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(nft_info.owner, Addr::unchecked(USER_2.to_string()));
+            assert!(!nft_info.is_end_reward);
+            assert_eq!(nft_info.pending_reward, Uint128::from(165u128));
+        }
+
+        #[test]
+        fn proper_operation_voting_power() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 10 + 2592000,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![
+                    LockupTerm {
+                        value: 1296000,
+                        percent: Uint128::new(50u128),
+                    },
+                    LockupTerm {
+                        value: 2592000,
+                        percent: Uint128::new(50u128),
+                    },
+                ],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: Some(true),
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // each user stake 1 nft, voting power is days of lockup term:
+            // 15 for nft 1, 30 for nft 2 and 3
+            let stake_height = app.block_info().height;
+            for (id, owner, lockup_term) in [
+                (1, USER_1, 1296000),
+                (2, USER_2, 2592000),
+                (3, USER_3, 2592000),
+            ] {
+                let stake_nft_msg = CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: id.to_string(),
+                        lockup_term,
+                    }],
+                };
+
+                // Execute stake nft to campaign
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked("contract3"),
+                    &stake_nft_msg,
+                    &[],
+                );
+
+                assert!(response.is_ok());
+            }
+
+            // power staked in a block is counted from the next block
+            let voting_power: VotingPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::VotingPowerAtHeight {
+                        address: USER_1.to_string(),
+                        height: Some(stake_height),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                voting_power,
+                VotingPowerAtHeightResponse {
+                    power: Uint128::zero(),
+                    height: stake_height,
+                }
+            );
+
+            // increase 15 days to end lockup term of nft 1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1296000),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 unstake nft 1
+            let unstake_height = app.block_info().height;
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 1 block
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(1),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 had power of nft 1 while it was staked
+            let voting_power: VotingPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::VotingPowerAtHeight {
+                        address: USER_1.to_string(),
+                        height: Some(unstake_height),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(voting_power.power, Uint128::from(15u128));
+
+            // USER_1 has no power after unstake
+            let voting_power: VotingPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::VotingPowerAtHeight {
+                        address: USER_1.to_string(),
+                        height: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(
+                voting_power,
+                VotingPowerAtHeightResponse {
+                    power: Uint128::zero(),
+                    height: unstake_height + 1,
+                }
+            );
+
+            // USER_2 keeps power of nft 2
+            let voting_power: VotingPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::VotingPowerAtHeight {
+                        address: USER_2.to_string(),
+                        height: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(voting_power.power, Uint128::from(30u128));
+
+            // total power of 3 nfts before unstake, 2 nfts after
+            let total_power: TotalPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPowerAtHeight {
+                        height: Some(unstake_height),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(total_power.power, Uint128::from(75u128));
+
+            let total_power: TotalPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::TotalPowerAtHeight { height: None },
+                )
+                .unwrap();
+
+            assert_eq!(
+                total_power,
+                TotalPowerAtHeightResponse {
+                    power: Uint128::from(60u128),
+                    height: unstake_height + 1,
+                }
+            );
+        }
+
+        #[test]
+        fn proper_operation_hooks() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // store and instantiate hook contract -> contract4
+            let hook_code_id = app.store_code(hook_contract_template());
+            let hook_contract = app
+                .instantiate_contract(
+                    hook_code_id,
+                    Addr::unchecked(ADMIN.to_string()),
+                    &Empty {},
+                    &[],
+                    "hook contract",
+                    None,
+                )
+                .unwrap();
+
+            // only owner of campaign can add hook
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddHook {
+                    addr: hook_contract.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // ADMIN add hook
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddHook {
+                    addr: hook_contract.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // hook can not be added twice
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddHook {
+                    addr: hook_contract.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            let hooks: HooksResponse = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::Hooks {})
+                .unwrap();
+
+            assert_eq!(
+                hooks,
+                HooksResponse {
+                    hooks: vec![hook_contract.to_string()],
+                }
+            );
+
+            // USER_1 stake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 299 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(299),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 unstake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::UnStakeNft {
+                    token_id: "1".to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 claim 100 reward
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(100u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // hook received stake, unstake and claim
+            let expected_hook_msgs = vec![
+                StakeChangedHookMsg::Stake {
+                    collection: Addr::unchecked(collection_contract.clone()),
+                    token_id: "1".to_string(),
+                    owner: Addr::unchecked(USER_1.to_string()),
+                    lockup_term: 299,
+                    amount: Uint128::one(),
+                },
+                StakeChangedHookMsg::Unstake {
+                    collection: Addr::unchecked(collection_contract.clone()),
+                    token_id: "1".to_string(),
+                    owner: Addr::unchecked(USER_1.to_string()),
+                    lockup_term: 299,
+                    amount: Uint128::one(),
+                    reward: Uint128::from(996u128),
+                },
+                StakeChangedHookMsg::Claim {
+                    collection: Addr::unchecked(collection_contract.clone()),
+                    owner: Addr::unchecked(USER_1.to_string()),
+                    amount: Uint128::from(100u128),
+                },
+            ];
+            let hook_msgs: Vec<StakeChangedHookMsg> = app
+                .wrap()
+                .query_wasm_smart(hook_contract.clone(), &Empty {})
+                .unwrap();
+
+            assert_eq!(hook_msgs, expected_hook_msgs);
+
+            // ADMIN remove hook
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::RemoveHook {
+                    addr: hook_contract.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let hooks: HooksResponse = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::Hooks {})
+                .unwrap();
+
+            assert_eq!(hooks, HooksResponse { hooks: vec![] });
+
+            // removed hook is not notified anymore
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(100u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let hook_msgs: Vec<StakeChangedHookMsg> = app
+                .wrap()
+                .query_wasm_smart(hook_contract, &Empty {})
+                .unwrap();
+
+            assert_eq!(hook_msgs, expected_hook_msgs);
+        }
+
+        #[test]
+        fn proper_operation_failing_hook() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // store and instantiate failing hook contract -> contract4
+            let hook_code_id = app.store_code(failing_hook_contract_template());
+            let hook_contract = app
+                .instantiate_contract(
+                    hook_code_id,
+                    Addr::unchecked(ADMIN.to_string()),
+                    &Empty {},
+                    &[],
+                    "failing hook contract",
+                    None,
+                )
+                .unwrap();
+
+            // ADMIN add hook
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::AddHook {
+                    addr: hook_contract.to_string(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 stake nft 1, failing hook does not block stake
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 299 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(299),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 unstake nft 1, failing hook does not block unstake
+            let response = app
+                .execute_contract(
+                    Addr::unchecked(USER_1.to_string()),
+                    Addr::unchecked("contract3"),
+                    &CampaignExecuteMsg::UnStakeNft {
+                        token_id: "1".to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            assert!(response.events.iter().any(|event| event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == "hook_failed")));
+
+            // nft 1 is transferred back to USER_1
+            let owner: cw721::OwnerOfResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    collection_contract.clone(),
+                    &cw721::Cw721QueryMsg::OwnerOf {
+                        token_id: "1".to_string(),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(owner.owner, USER_1.to_string());
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.total_nft_staked, 0);
+        }
+
+        #[test]
+        fn proper_operation_campaigns_by_index() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // other collection and reward token, only addresses are stored by campaign
+            let other_collection = "collection2";
+            let other_token = "token2";
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign 1 (ADMIN, collection, token), campaign 2 (USER_1, collection,
+            // other token) and campaign 3 (ADMIN, other collection, token)
+            for (owner, collection, token) in [
+                (ADMIN, collection_contract.as_str(), token_contract.as_str()),
+                (USER_1, collection_contract.as_str(), other_token),
+                (ADMIN, other_collection, token_contract.as_str()),
+            ] {
+                let create_campaign = CreateCampaign {
+                    owner: owner.to_string(),
+                    campaign_name: "campaign name".to_string(),
+                    campaign_image: "campaign name".to_string(),
+                    campaign_description: "campaign name".to_string(),
+                    start_time: current_block_time + 10,
+                    end_time: current_block_time + 310,
+                    limit_per_staker: 2,
+                    reward_token_info: AssetToken {
+                        info: TokenInfo::Token {
+                            contract_addr: token.to_string(),
+                        },
+                        amount: Uint128::zero(),
+                    },
+                    allowed_collection: collection.to_string(),
+                    lockup_term: vec![LockupTerm {
+                        value: 299,
+                        percent: Uint128::new(100u128),
+                    }],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
+                };
+
+                // Execute create campaign
+                let response_create_campaign = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(factory_contract.clone()),
+                    &crate::msg::ExecuteMsg::CreateCampaign {
+                        create_campaign,
+                        salt: None,
+                    },
+                    &[],
+                );
+
+                assert!(response_create_campaign.is_ok());
+            }
+
+            let campaign_1 = FactoryCampaignResponse {
+                owner: Addr::unchecked(ADMIN.to_string()),
+                campaign_addr: Addr::unchecked("contract3"),
+                reward_token: TokenInfo::Token {
+                    contract_addr: token_contract.to_string(),
+                },
+                allowed_collection: Addr::unchecked(collection_contract.clone()),
+                campaign_id: 1,
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                cancelled: false,
+                verified: false,
+            };
+            let campaign_2 = FactoryCampaignResponse {
+                owner: Addr::unchecked(USER_1.to_string()),
+                campaign_addr: Addr::unchecked("contract4"),
+                reward_token: TokenInfo::Token {
+                    contract_addr: other_token.to_string(),
+                },
+                allowed_collection: Addr::unchecked(collection_contract.clone()),
+                campaign_id: 2,
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                cancelled: false,
+                verified: false,
+            };
+            let campaign_3 = FactoryCampaignResponse {
+                owner: Addr::unchecked(ADMIN.to_string()),
+                campaign_addr: Addr::unchecked("contract5"),
+                reward_token: TokenInfo::Token {
+                    contract_addr: token_contract.to_string(),
+                },
+                allowed_collection: Addr::unchecked(other_collection),
+                campaign_id: 3,
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                cancelled: false,
+                verified: false,
+            };
+
+            // campaigns of ADMIN
+            let campaigns: Vec<FactoryCampaignResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignsByOwner {
+                        owner: ADMIN.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaigns, vec![campaign_1.clone(), campaign_3.clone()]);
+
+            // next page of campaigns of ADMIN
+            let campaigns: Vec<FactoryCampaignResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignsByOwner {
+                        owner: ADMIN.to_string(),
+                        start_after: Some(1),
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaigns, vec![campaign_3.clone()]);
+
+            // campaigns accepting nfts of collection
+            let campaigns: Vec<FactoryCampaignResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignsByCollection {
+                        collection: collection_contract.clone(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaigns, vec![campaign_1.clone(), campaign_2.clone()]);
+
+            // campaigns rewarding token
+            let campaigns: Vec<FactoryCampaignResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignsByRewardToken {
+                        reward_token: TokenInfo::Token {
+                            contract_addr: token_contract.to_string(),
+                        },
+                        start_after: None,
+                        limit: Some(1),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaigns, vec![campaign_1]);
+
+            let campaigns: Vec<FactoryCampaignResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignsByRewardToken {
+                        reward_token: TokenInfo::Token {
+                            contract_addr: other_token.to_string(),
+                        },
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaigns, vec![campaign_2.clone()]);
+
+            // no campaign for unknown owner
+            let campaigns: Vec<FactoryCampaignResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignsByOwner {
+                        owner: USER_2.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert!(campaigns.is_empty());
+
+            // reverse lookup of campaign by its address
+            let campaign: FactoryCampaignResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignByAddr {
+                        addr: "contract4".to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaign, campaign_2);
+
+            // unknown address is not a campaign
+            let response: Result<FactoryCampaignResponse, _> = app.wrap().query_wasm_smart(
+                factory_contract.clone(),
+                &QueryMsg::CampaignByAddr {
+                    addr: collection_contract.clone(),
+                },
+            );
+
+            assert!(response.is_err());
+
+            // paginate campaign addresses
+            let campaign_addrs: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignAddrs {
+                        start_after: None,
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaign_addrs, vec!["contract3", "contract4"]);
+
+            let campaign_addrs: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignAddrs {
+                        start_after: Some("contract4".to_string()),
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaign_addrs, vec!["contract5"]);
+        }
+
+        #[test]
+        fn proper_operation_cancel_campaign() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: Some(vec![
+                    EmissionPhase {
+                        start_time: current_block_time + 10,
+                        weight: Uint128::new(3u128),
+                    },
+                    EmissionPhase {
+                        start_time: current_block_time + 110,
+                        weight: Uint128::new(1u128),
+                    },
+                ]),
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // campaign can only be cancelled through factory
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::Cancel {},
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // ADMIN cancel campaign 1 before it starts
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CancelCampaign { campaign_id: 1 },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // campaign ends now
+            let cancel_time = app.block_info().time.seconds();
+            let campaign: FactoryCampaignResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::Campaign { campaign_id: 1 },
+                )
+                .unwrap();
+
+            assert!(campaign.cancelled);
+            assert_eq!(campaign.start_time, cancel_time);
+            assert_eq!(campaign.end_time, cancel_time);
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.end_time, cancel_time);
+
+            // emission phases after the new end time are cut
+            assert_eq!(campaign_info.emission_schedule, vec![]);
+
+            let emission_schedule: EmissionScheduleResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::EmissionSchedule {})
+                .unwrap();
+
+            assert_eq!(
+                emission_schedule,
+                EmissionScheduleResult {
+                    current_reward_per_second: Uint128::zero(),
+                    phases: vec![],
+                }
+            );
+
+            // increase 11 second -> s1 of campaign 1 before cancel
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 can not stake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // ADMIN withdraw all 1000 unit of reward
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::WithdrawReward {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(1000u128));
+
+            // create campaign 2 from s21 to s321 -> contract4
+            let current_block_time = app.block_info().time.seconds();
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: CreateCampaign {
+                        start_time: current_block_time + 10,
+                        end_time: current_block_time + 310,
+                        emission_schedule: None,
+                        ..create_campaign
+                    },
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign 2
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // started campaign can not be cancelled, stakers may have locked nfts for its reward
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CancelCampaign { campaign_id: 2 },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast_ref::<ContractError>(),
+                Some(&ContractError::CampaignStarted {})
+            );
+        }
+
+        #[test]
+        fn proper_operation_campaigns_by_status() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // campaign 1 and 3 run from s10 to s310, campaign 2 from s100 to s400
+            for (owner, start_time, end_time) in [
+                (ADMIN, current_block_time + 10, current_block_time + 310),
+                (ADMIN, current_block_time + 100, current_block_time + 400),
+                (USER_1, current_block_time + 10, current_block_time + 310),
+            ] {
+                let create_campaign = CreateCampaign {
+                    owner: owner.to_string(),
+                    campaign_name: "campaign name".to_string(),
+                    campaign_image: "campaign name".to_string(),
+                    campaign_description: "campaign name".to_string(),
+                    start_time,
+                    end_time,
+                    limit_per_staker: 2,
+                    reward_token_info: AssetToken {
+                        info: TokenInfo::Token {
+                            contract_addr: token_contract.to_string(),
+                        },
+                        amount: Uint128::zero(),
+                    },
+                    allowed_collection: collection_contract.clone(),
+                    lockup_term: vec![LockupTerm {
+                        value: 299,
+                        percent: Uint128::new(100u128),
+                    }],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
+                };
+
+                // Execute create campaign
+                let response_create_campaign = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(factory_contract.clone()),
+                    &crate::msg::ExecuteMsg::CreateCampaign {
+                        create_campaign,
+                        salt: None,
+                    },
+                    &[],
+                );
+
+                assert!(response_create_campaign.is_ok());
+            }
+
+            // only factory owner or campaign owner can cancel campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CancelCampaign { campaign_id: 1 },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // USER_1 cancel campaign 3
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CancelCampaign { campaign_id: 3 },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // campaign can not be cancelled twice
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CancelCampaign { campaign_id: 3 },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // ids of campaigns with status at current block time
+            let campaign_ids = |app: &App, status: CampaignStatus| -> Vec<u64> {
+                app.wrap()
+                    .query_wasm_smart::<CampaignsResponse>(
+                        factory_contract.clone(),
+                        &QueryMsg::Campaigns {
+                            status: Some(status),
+                            start_after: None,
+                            limit: None,
+                        },
+                    )
+                    .unwrap()
+                    .campaigns
+                    .into_iter()
+                    .map(|campaign| campaign.campaign_id)
+                    .collect()
+            };
+
+            assert_eq!(campaign_ids(&app, CampaignStatus::Upcoming), vec![1, 2]);
+            assert!(campaign_ids(&app, CampaignStatus::Active).is_empty());
+            assert_eq!(campaign_ids(&app, CampaignStatus::Cancelled), vec![3]);
+
+            // pages of upcoming campaigns continue after the last scanned campaign
+            let mut start_after = None;
+            let mut pages = vec![];
+            loop {
+                let res: CampaignsResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        factory_contract.clone(),
+                        &QueryMsg::Campaigns {
+                            status: Some(CampaignStatus::Upcoming),
+                            start_after,
+                            limit: Some(1),
+                        },
+                    )
+                    .unwrap();
+                pages.push((
+                    res.campaigns
+                        .into_iter()
+                        .map(|campaign| campaign.campaign_id)
+                        .collect::<Vec<_>>(),
+                    res.next_start_after,
+                ));
+                match res.next_start_after {
+                    Some(next) => start_after = Some(next),
+                    None => break,
+                }
+            }
+
+            assert_eq!(
+                pages,
+                vec![(vec![1], Some(1)), (vec![2], Some(2)), (vec![], None),]
+            );
+
+            // increase 11 second -> s11
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            assert_eq!(campaign_ids(&app, CampaignStatus::Upcoming), vec![2]);
+            assert_eq!(campaign_ids(&app, CampaignStatus::Active), vec![1]);
+            assert!(campaign_ids(&app, CampaignStatus::Ended).is_empty());
+
+            // increase 299 second -> s310
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(299),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            assert!(campaign_ids(&app, CampaignStatus::Upcoming).is_empty());
+            assert_eq!(campaign_ids(&app, CampaignStatus::Active), vec![2]);
+            assert_eq!(campaign_ids(&app, CampaignStatus::Ended), vec![1]);
+            assert_eq!(campaign_ids(&app, CampaignStatus::Cancelled), vec![3]);
+        }
+
+        #[test]
+        fn proper_operation_creation_fee() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // anyone can create campaign paying 100 token to USER_3, USER_2 is exempt
+            let cw20_fee = AssetToken {
+                info: TokenInfo::Token {
+                    contract_addr: token_contract.to_string(),
+                },
+                amount: Uint128::from(100u128),
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: Some(true),
+                    creation_fee: Some(cw20_fee.clone()),
+                    treasury: Some(Addr::unchecked(USER_3)),
+                    fee_exempt: Some(vec![Addr::unchecked(USER_2)]),
+                    protocol_fee_bps: None,
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let config: ConfigResponse = app
+                .wrap()
+                .query_wasm_smart(factory_contract.clone(), &crate::msg::QueryMsg::Config {})
+                .unwrap();
+
+            assert_eq!(
+                config,
+                ConfigResponse {
+                    owner: ADMIN.to_string(),
+                    campaign_code_id: 4,
+                    creation_fee: Some(cw20_fee),
+                    treasury: USER_3.to_string(),
+                    fee_exempt: vec![USER_2.to_string()],
+                    protocol_fee_bps: 0,
+                    allow_unverified: true,
+                }
+            );
+
+            // USER_1 can not create campaign without paying fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // mint 100 token to USER_1 and approve factory to take fee
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: USER_1.to_string(),
+                    amount: Uint128::from(100u128),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: factory_contract.to_string(),
+                    amount: Uint128::from(100u128),
+                    expires: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 create campaign paying fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // fee is sent to treasury
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_3.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(100u128));
+
+            // USER_2 create campaign without paying fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // fee is changed to 50 native token
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: None,
+                    creation_fee: Some(AssetToken {
+                        info: TokenInfo::NativeToken {
+                            denom: "uaura".to_string(),
+                        },
+                        amount: Uint128::from(50u128),
+                    }),
+                    treasury: None,
+                    fee_exempt: None,
+                    protocol_fee_bps: None,
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint 100 native token to USER_1
+            app.sudo(SudoMsg::Bank(BankSudo::Mint {
+                to_address: USER_1.to_string(),
+                amount: coins(100, "uaura"),
+            }))
+            .unwrap();
+
+            // USER_1 can not create campaign paying wrong amount of fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &coins(40, "uaura"),
+            );
+
+            assert!(response.is_err());
+
+            // USER_1 create campaign paying native fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &coins(50, "uaura"),
+            );
+
+            assert!(response.is_ok());
+
+            let balance = app.wrap().query_balance(USER_3, "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(50u128));
+
+            // mint 10 native token to USER_2
+            app.sudo(SudoMsg::Bank(BankSudo::Mint {
+                to_address: USER_2.to_string(),
+                amount: coins(10, "uaura"),
+            }))
+            .unwrap();
+
+            // USER_2 is exempt, native token sent without fee would be stuck in factory
+            let response = app.execute_contract(
+                Addr::unchecked(USER_2.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &coins(10, "uaura"),
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast_ref::<ContractError>(),
+                Some(&ContractError::Payment(PaymentError::NonPayable {}))
+            );
+
+            let balance = app.wrap().query_balance(USER_2, "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(10u128));
+
+            // 3 campaigns are created
+            let campaign_addrs: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignAddrs {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(campaign_addrs.len(), 3);
+        }
+
+        #[test]
+        fn proper_operation_protocol_fee() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // Mint 1000 unit of token to ADMIN
+            let mint_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::Mint {
+                recipient: ADMIN.to_string(),
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute minting
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &mint_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint nft token_id 1 to USER_1, token_id 2 to USER_2, token_id 3 to USER_3
+            for (id, owner) in [(1, USER_1), (2, USER_2), (3, USER_3)] {
+                let mint_nft_msg = Cw721MintMsg {
+                    token_id: id.to_string(),
+                    owner: owner.to_string(),
+                    token_uri: Some(
+                        "https://starships.example.com/Starship/Enterprise.json".into(),
+                    ),
+                    extension: Some(Metadata {
+                        description: Some("Spaceship with Warp Drive".into()),
+                        name: Some("Starship USS Enterprise".to_string()),
+                        ..Metadata::default()
+                    }),
+                };
+
+                let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+                let response_mint_nft = app.execute_contract(
+                    Addr::unchecked(ADMIN.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &exec_msg,
+                    &[],
+                );
+
+                assert!(response_mint_nft.is_ok());
+
+                // Approve all nft of owner to campaign contract
+                let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                    operator: "contract3".to_string(),
+                    expires: None,
+                };
+
+                // Execute approve nft
+                let response = app.execute_contract(
+                    Addr::unchecked(owner.to_string()),
+                    Addr::unchecked(collection_contract.clone()),
+                    &approve_msg,
+                    &[],
+                );
+                assert!(response.is_ok());
+            }
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            // protocol fee can not exceed 10%
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: None,
+                    creation_fee: None,
+                    treasury: None,
+                    fee_exempt: None,
+                    protocol_fee_bps: Some(2000),
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // new campaigns pay 5% of claimed reward to USER_3
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: None,
+                    creation_fee: None,
+                    treasury: Some(Addr::unchecked(USER_3)),
+                    fee_exempt: None,
+                    protocol_fee_bps: Some(500),
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // create campaign contract by factory contract
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // Execute create campaign
+            let response_create_campaign = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response_create_campaign.is_ok());
+
+            // Approve cw20 token to campaign contract
+            let approve_msg: Cw20ExecuteMsg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: "contract3".to_string(), // Campaign Contract
+                amount: Uint128::from(1000u128),
+                expires: None,
+            };
+
+            // Execute approve
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // add 1000 unit of reward token, 3.33 unit per second
+            let add_reward_balance_msg = CampaignExecuteMsg::AddRewardToken {
+                amount: Uint128::from(1000u128),
+            };
+
+            // Execute add reward balance
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &add_reward_balance_msg,
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 11 second to make active campaign -> s1
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // increase 299 second to end campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(299),
+                height: app.block_info().height + 299,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 claim 200 reward, 10 is protocol fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: Uint128::from(200u128),
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            for (address, amount) in [(USER_1, 190u128), (USER_3, 10u128)] {
+                let balance: BalanceResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        token_contract.clone(),
+                        &cw20::Cw20QueryMsg::Balance {
+                            address: address.to_string(),
+                        },
+                    )
+                    .unwrap();
+
+                assert_eq!(balance.balance, Uint128::from(amount));
+            }
+
+            // staked info reports gross, fee and net reward claimed
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(staked_info.reward_debt, Uint128::from(796u128));
+            assert_eq!(staked_info.reward_claimed, Uint128::from(200u128));
+            assert_eq!(staked_info.protocol_fee_paid, Uint128::from(10u128));
+            assert_eq!(staked_info.net_reward_claimed, Uint128::from(190u128));
+
+            // campaign counts gross reward as claimed
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.protocol_fee_bps, 500);
+            assert_eq!(
+                campaign_info.protocol_fee_recipient,
+                Some(Addr::unchecked(USER_3.to_string()))
+            );
+            assert_eq!(campaign_info.total_reward_claimed, Uint128::from(200u128));
+            assert_eq!(
+                campaign_info.reward_token_info.amount,
+                Uint128::from(800u128)
+            );
+        }
+
+        // creator funds campaign at creation by sending reward token to factory
+        #[test]
+        fn proper_operation_create_funded_campaign() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // unverified token can not fund campaign, its sender is not trusted
+            let mut fake_token_campaign = create_campaign.clone();
+            fake_token_campaign.reward_token_info.info = TokenInfo::Token {
+                contract_addr: USER_1.to_string(),
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::Receive(Cw20ReceiveMsg {
+                    sender: ADMIN.to_string(),
+                    amount: Uint128::zero(),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: fake_token_campaign,
+                        salt: None,
+                    })
+                    .unwrap(),
+                }),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast_ref::<ContractError>(),
+                Some(&ContractError::Unverified {})
+            );
+
+            // reward token is verified, cw20 sender is trusted as creator
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedRewardTokens {
+                    add: vec![TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    }],
+                    remove: vec![],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // verified token must send reward
+            let response = app.execute_contract(
+                Addr::unchecked(token_contract.clone()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::Receive(Cw20ReceiveMsg {
+                    sender: ADMIN.to_string(),
+                    amount: Uint128::zero(),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: create_campaign.clone(),
+                        salt: None,
+                    })
+                    .unwrap(),
+                }),
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast_ref::<ContractError>(),
+                Some(&ContractError::InvalidFunds {})
+            );
+
+            // mint 1000 token to ADMIN
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: ADMIN.to_string(),
+                    amount: Uint128::from(1000u128),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // sent token is not the reward token of campaign
+            let mut other_token_campaign = create_campaign.clone();
+            other_token_campaign.reward_token_info.info = TokenInfo::Token {
+                contract_addr: "other_token".to_string(),
+            };
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: factory_contract.to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: other_token_campaign,
+                        salt: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // ADMIN create campaign sending 1000 reward token
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: factory_contract.to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: create_campaign.clone(),
+                        salt: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // reward is deposited to campaign
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.owner, Addr::unchecked(ADMIN));
+            assert_eq!(
+                campaign_info.reward_token_info.amount,
+                Uint128::from(1000u128)
+            );
+            assert_eq!(campaign_info.total_reward, Uint128::from(1000u128));
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: "contract3".to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(1000u128));
+
+            // factory keeps no token
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: factory_contract.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::zero());
+
+            // campaign created without sending token has no reward
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract4", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(campaign_info.reward_token_info.amount, Uint128::zero());
+        }
+
+        // creation fee is collected when campaign is funded by cw20 send
+        #[test]
+        fn proper_operation_create_funded_campaign_with_fee() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // anyone can create campaign paying 50 native token to USER_3
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: Some(true),
+                    creation_fee: Some(AssetToken {
+                        info: TokenInfo::NativeToken {
+                            denom: "uaura".to_string(),
+                        },
+                        amount: Uint128::from(50u128),
+                    }),
+                    treasury: Some(Addr::unchecked(USER_3)),
+                    fee_exempt: None,
+                    protocol_fee_bps: None,
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // reward token is verified, cw20 sender is trusted as creator
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedRewardTokens {
+                    add: vec![TokenInfo::Token {
+                        contract_addr: token_contract.to_string(),
+                    }],
+                    remove: vec![],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint 2000 token to USER_1
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Mint {
+                    recipient: USER_1.to_string(),
+                    amount: Uint128::from(2000u128),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // native fee can not be paid with cw20 send, USER_1 can not skip it
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: factory_contract.to_string(),
+                    amount: Uint128::from(1u128),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: create_campaign.clone(),
+                        salt: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert_eq!(
+                response.unwrap_err().downcast_ref::<ContractError>(),
+                Some(&ContractError::InvalidCreationFee {})
+            );
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_1.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(2000u128));
+
+            // creator exempt from fee creates campaign with cw20 send
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: None,
+                    creation_fee: None,
+                    treasury: None,
+                    fee_exempt: Some(vec![Addr::unchecked(USER_1)]),
+                    protocol_fee_bps: None,
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: factory_contract.to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: create_campaign.clone(),
+                        salt: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(
+                campaign_info.reward_token_info.amount,
+                Uint128::from(1000u128)
+            );
+
+            let balance = app.wrap().query_balance(USER_3, "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::zero());
+
+            // fee is changed to 100 reward token
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: None,
+                    creation_fee: Some(AssetToken {
+                        info: TokenInfo::Token {
+                            contract_addr: token_contract.to_string(),
+                        },
+                        amount: Uint128::from(100u128),
+                    }),
+                    treasury: Some(Addr::unchecked(USER_3)),
+                    fee_exempt: Some(vec![]),
+                    protocol_fee_bps: None,
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // sent token does not cover fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: factory_contract.to_string(),
+                    amount: Uint128::from(50u128),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: create_campaign.clone(),
+                        salt: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // fee is taken from sent token, the rest is reward of campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(token_contract.clone()),
+                &Cw20ExecuteMsg::Send {
+                    contract: factory_contract.to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_binary(&crate::msg::Cw20HookMsg::CreateCampaign {
+                        create_campaign: create_campaign.clone(),
+                        salt: None,
+                    })
+                    .unwrap(),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract4", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(
+                campaign_info.reward_token_info.amount,
+                Uint128::from(900u128)
+            );
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: USER_3.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(100u128));
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: "contract4".to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(900u128));
+
+            let balance: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token_contract.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: factory_contract.to_string(),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(balance.balance, Uint128::from(0u128));
+        }
+
+        // native reward sent with create campaign is deposited to campaign, net of creation fee
+        #[test]
+        fn proper_operation_create_native_funded_campaign() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // mint nft token_id 1 to USER_1
+            let (id, owner) = (1, USER_1);
+            let mint_nft_msg = Cw721MintMsg {
+                token_id: id.to_string(),
+                owner: owner.to_string(),
+                token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
+                extension: Some(Metadata {
+                    description: Some("Spaceship with Warp Drive".into()),
+                    name: Some("Starship USS Enterprise".to_string()),
+                    ..Metadata::default()
+                }),
+            };
+
+            let exec_msg = Cw721ExecuteMsg::Mint(mint_nft_msg);
+
+            let response_mint_nft = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(collection_contract.clone()),
+                &exec_msg,
+                &[],
+            );
+
+            assert!(response_mint_nft.is_ok());
+
+            // Approve all nft of owner to campaign contract
+            let approve_msg: Cw721ExecuteMsg = Cw721ExecuteMsg::ApproveAll {
+                operator: "contract3".to_string(),
+                expires: None,
+            };
+
+            // Execute approve nft
+            let response = app.execute_contract(
+                Addr::unchecked(owner.to_string()),
+                Addr::unchecked(collection_contract.clone()),
+                &approve_msg,
+                &[],
+            );
+            assert!(response.is_ok());
+
+            // anyone can create campaign paying 50 native token to USER_3
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: Some(true),
+                    creation_fee: Some(AssetToken {
+                        info: TokenInfo::NativeToken {
+                            denom: "uaura".to_string(),
+                        },
+                        amount: Uint128::from(50u128),
+                    }),
+                    treasury: Some(Addr::unchecked(USER_3)),
+                    fee_exempt: None,
+                    protocol_fee_bps: None,
+                    allow_unverified: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // mint 1090 native token to USER_1
+            app.sudo(SudoMsg::Bank(BankSudo::Mint {
+                to_address: USER_1.to_string(),
+                amount: coins(1090, "uaura"),
+            }))
+            .unwrap();
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: TokenInfo::NativeToken {
+                        denom: "uaura".to_string(),
+                    },
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // sent native token does not cover fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &coins(40, "uaura"),
+            );
+
+            assert!(response.is_err());
+
+            // USER_1 create campaign sending 1050 native token, 50 is fee
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &coins(1050, "uaura"),
+            );
+
+            assert!(response.is_ok());
+
+            // 1000 native token is deposited to campaign
+            let campaign_info: CampaignInfoResult = app
+                .wrap()
+                .query_wasm_smart("contract3", &CampaignQueryMsg::CampaignInfo {})
+                .unwrap();
+
+            assert_eq!(
+                campaign_info.reward_token_info.amount,
+                Uint128::from(1000u128)
+            );
+            assert_eq!(campaign_info.total_reward, Uint128::from(1000u128));
+
+            let balance = app.wrap().query_balance("contract3", "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(1000u128));
+
+            let balance = app.wrap().query_balance(USER_3, "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(50u128));
+
+            let balance = app
+                .wrap()
+                .query_balance(factory_contract.clone(), "uaura")
+                .unwrap();
+
+            assert_eq!(balance.amount, Uint128::zero());
+
+            // increase 11 second to make active campaign
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(11),
+                height: app.block_info().height + 11,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // USER_1 stake nft 1
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::StakeNfts {
+                    nfts: vec![NftStake {
+                        token_id: "1".to_string(),
+                        lockup_term: 299,
+                    }],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // campaign ends
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(300),
+                height: app.block_info().height + 300,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let staked_info: StakedInfoResult = app
+                .wrap()
+                .query_wasm_smart(
+                    "contract3",
+                    &CampaignQueryMsg::NftStaked {
+                        owner: Addr::unchecked(USER_1.to_string()),
+                        start_after: None,
+                        limit: None,
+                        at_time: None,
+                    },
+                )
+                .unwrap();
+            let reward = staked_info.reward_debt;
+
+            assert!(!reward.is_zero());
+
+            // USER_1 claim reward in native token
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::ClaimReward {
+                    amount: reward,
+                    receipt_ids: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let balance = app.wrap().query_balance(USER_1, "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(40u128) + reward);
+
+            // ADMIN withdraw remaining reward in native token
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked("contract3"),
+                &CampaignExecuteMsg::WithdrawReward {},
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let balance = app.wrap().query_balance(ADMIN, "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::from(1000u128) - reward);
+
+            let balance = app.wrap().query_balance("contract3", "uaura").unwrap();
+
+            assert_eq!(balance.amount, Uint128::zero());
+        }
+
+        // owner verifies collections and reward tokens, unverified campaigns can be rejected
+        #[test]
+        fn proper_operation_verified_registries() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+
+            // get factory contract
+            let factory_contract = &contracts[0].contract_addr;
+            // get lp token contract
+            let token_contract = &contracts[1].contract_addr;
+            // get collection contract
+            let collection_contract = &contracts[2].contract_addr;
+
+            // get current block time
+            let current_block_time = app.block_info().time.seconds();
+
+            let reward_token = TokenInfo::Token {
+                contract_addr: token_contract.to_string(),
+            };
+            let create_campaign = CreateCampaign {
+                owner: ADMIN.to_string(),
+                campaign_name: "campaign name".to_string(),
+                campaign_image: "campaign name".to_string(),
+                campaign_description: "campaign name".to_string(),
+                start_time: current_block_time + 10,
+                end_time: current_block_time + 310,
+                limit_per_staker: 2,
+                reward_token_info: AssetToken {
+                    info: reward_token.clone(),
+                    amount: Uint128::zero(),
+                },
+                allowed_collection: collection_contract.clone(),
+                lockup_term: vec![LockupTerm {
+                    value: 299,
+                    percent: Uint128::new(100u128),
+                }],
+                distribution_mode: None,
+                loyalty_steps: None,
+                emission_schedule: None,
+                rollover_unallocated: None,
+                receipt_code_id: None,
+                reward_mode: None,
+                soft_staking: None,
+                voting_power_by_lockup: None,
+            };
+
+            // only owner can update registries
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedCollections {
+                    add: vec![collection_contract.to_string()],
+                    remove: vec![],
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // reject campaigns of unverified assets
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: Some(true),
+                    creation_fee: None,
+                    treasury: None,
+                    fee_exempt: None,
+                    protocol_fee_bps: None,
+                    allow_unverified: Some(false),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // collection is verified, reward token is not
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedCollections {
+                    add: vec![collection_contract.to_string()],
+                    remove: vec![],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // cw20 reward token must have a valid address
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedRewardTokens {
+                    add: vec![TokenInfo::Token {
+                        contract_addr: "Contract1".to_string(),
+                    }],
+                    remove: vec![],
+                },
+                &[],
+            );
+
+            assert!(response.is_err());
+
+            // reward token is verified
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedRewardTokens {
+                    add: vec![reward_token.clone()],
+                    remove: vec![],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let collections: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::VerifiedCollections {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(collections, vec![collection_contract.to_string()]);
+
+            let reward_tokens: Vec<TokenInfo> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::VerifiedRewardTokens {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(reward_tokens, vec![reward_token.clone()]);
+
+            // USER_1 create verified campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign: create_campaign.clone(),
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let campaign: FactoryCampaignResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::Campaign { campaign_id: 1 },
+                )
+                .unwrap();
+
+            assert!(campaign.verified);
+
+            // reward token is removed, unverified campaigns are allowed again
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedRewardTokens {
+                    add: vec![],
+                    remove: vec![reward_token.clone()],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // campaign 1 is no longer verified
+            let campaign: FactoryCampaignResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::Campaign { campaign_id: 1 },
+                )
+                .unwrap();
+
+            assert!(!campaign.verified);
+
+            let campaigns: Vec<FactoryCampaignResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::CampaignsByCollection {
+                        collection: collection_contract.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert!(!campaigns[0].verified);
+
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateConfig {
+                    owner: None,
+                    campaign_code_id: None,
+                    allow_create_for_all: None,
+                    creation_fee: None,
+                    treasury: None,
+                    fee_exempt: None,
+                    protocol_fee_bps: None,
+                    allow_unverified: Some(true),
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            // USER_1 create unverified campaign
+            let response = app.execute_contract(
+                Addr::unchecked(USER_1.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::CreateCampaign {
+                    create_campaign,
+                    salt: None,
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let campaign: FactoryCampaignResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::Campaign { campaign_id: 2 },
+                )
+                .unwrap();
+
+            assert!(!campaign.verified);
+
+            // reward token is verified again, both campaigns are verified
+            let response = app.execute_contract(
+                Addr::unchecked(ADMIN.to_string()),
+                Addr::unchecked(factory_contract.clone()),
+                &crate::msg::ExecuteMsg::UpdateVerifiedRewardTokens {
+                    add: vec![reward_token],
+                    remove: vec![],
+                },
+                &[],
+            );
+
+            assert!(response.is_ok());
+
+            let campaigns: CampaignsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    factory_contract.clone(),
+                    &QueryMsg::Campaigns {
+                        status: None,
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            assert!(campaigns.campaigns.iter().all(|campaign| campaign.verified));
+            assert_eq!(campaigns.campaigns.len(), 2);
+        }
     }
 }
@@ -1,3 +1,3 @@
-#[cfg(test)]
 mod env_setup;
 mod integration_test;
+mod predict_address_test;
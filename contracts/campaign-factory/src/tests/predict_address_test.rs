@@ -0,0 +1,234 @@
+#[cfg(test)]
+mod tests {
+    use campaign::state::{AssetToken, LockupTerm, TokenInfo};
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        from_binary, instantiate2_address, to_binary, Addr, Api, Binary, CanonicalAddr,
+        CodeInfoResponse, ContractResult, CosmosMsg, Env, HexBinary, OwnedDeps, RecoverPubkeyError,
+        StdResult, SystemResult, Uint128, VerificationError, WasmMsg, WasmQuery,
+    };
+
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use crate::state::CreateCampaign;
+    use crate::tests::env_setup::env::{ADMIN, USER_1, USER_2};
+
+    const CAMPAIGN_CODE_ID: u64 = 2;
+    const CHECKSUM: &str = "13a1fc994cc6d1c81b746ee0c0ff6f90043875e0bf1d9be6b7d779fc978dc2a5";
+
+    // MockApi only humanizes its own canonical length, instantiate2 addresses have 32 bytes
+    #[derive(Copy, Clone, Default)]
+    struct Instantiate2Api(MockApi);
+
+    impl Api for Instantiate2Api {
+        fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+            self.0.addr_validate(human)
+        }
+
+        fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+            self.0.addr_canonicalize(human)
+        }
+
+        fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+            match canonical.len() {
+                32 => Ok(Addr::unchecked(
+                    HexBinary::from(canonical.as_slice()).to_hex(),
+                )),
+                _ => self.0.addr_humanize(canonical),
+            }
+        }
+
+        fn secp256k1_verify(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.secp256k1_verify(message_hash, signature, public_key)
+        }
+
+        fn secp256k1_recover_pubkey(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            recovery_param: u8,
+        ) -> Result<Vec<u8>, RecoverPubkeyError> {
+            self.0
+                .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+        }
+
+        fn ed25519_verify(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.ed25519_verify(message, signature, public_key)
+        }
+
+        fn ed25519_batch_verify(
+            &self,
+            messages: &[&[u8]],
+            signatures: &[&[u8]],
+            public_keys: &[&[u8]],
+        ) -> Result<bool, VerificationError> {
+            self.0
+                .ed25519_batch_verify(messages, signatures, public_keys)
+        }
+
+        fn debug(&self, message: &str) {
+            self.0.debug(message)
+        }
+    }
+
+    fn mock_dependencies() -> OwnedDeps<MockStorage, Instantiate2Api, MockQuerier> {
+        let mut querier = MockQuerier::default();
+
+        // chain answers checksum of campaign code
+        querier.update_wasm(|query| match query {
+            WasmQuery::CodeInfo { code_id } if *code_id == CAMPAIGN_CODE_ID => {
+                let mut code_info = CodeInfoResponse::default();
+                code_info.code_id = CAMPAIGN_CODE_ID;
+                code_info.creator = ADMIN.to_string();
+                code_info.checksum = HexBinary::from_hex(CHECKSUM).unwrap();
+                SystemResult::Ok(ContractResult::Ok(to_binary(&code_info).unwrap()))
+            }
+            _ => panic!("unexpected query"),
+        });
+
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: Instantiate2Api::default(),
+            querier,
+            custom_query_type: Default::default(),
+        }
+    }
+
+    // campaign created with salt by creator, returns instantiate2 message of campaign
+    fn create_salted_campaign(
+        deps: &mut OwnedDeps<MockStorage, Instantiate2Api, MockQuerier>,
+        env: &Env,
+        creator: &str,
+        salt: &Binary,
+    ) -> WasmMsg {
+        let current_time = env.block.time.seconds();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(creator, &[]),
+            ExecuteMsg::CreateCampaign {
+                create_campaign: CreateCampaign {
+                    owner: creator.to_string(),
+                    campaign_name: "campaign name".to_string(),
+                    campaign_image: "campaign name".to_string(),
+                    campaign_description: "campaign name".to_string(),
+                    start_time: current_time + 10,
+                    end_time: current_time + 310,
+                    limit_per_staker: 2,
+                    reward_token_info: AssetToken {
+                        info: TokenInfo::Token {
+                            contract_addr: "reward_token".to_string(),
+                        },
+                        amount: Uint128::zero(),
+                    },
+                    allowed_collection: "collection".to_string(),
+                    lockup_term: vec![LockupTerm {
+                        value: 299,
+                        percent: Uint128::new(100u128),
+                    }],
+                    distribution_mode: None,
+                    loyalty_steps: None,
+                    emission_schedule: None,
+                    rollover_unallocated: None,
+                    receipt_code_id: None,
+                    reward_mode: None,
+                    soft_staking: None,
+                    voting_power_by_lockup: None,
+                },
+                salt: Some(salt.clone()),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            CosmosMsg::Wasm(msg) => msg.clone(),
+            msg => panic!("unexpected message {:?}", msg),
+        }
+    }
+
+    #[test]
+    fn predict_campaign_address() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            InstantiateMsg {
+                campaign_code_id: CAMPAIGN_CODE_ID,
+                allow_create_for_all: true,
+                creation_fee: None,
+                treasury: None,
+                fee_exempt: None,
+                protocol_fee_bps: None,
+                allow_unverified: None,
+            },
+        )
+        .unwrap();
+
+        let salt = Binary::from(b"campaign".to_vec());
+        let predict =
+            |deps: &OwnedDeps<MockStorage, Instantiate2Api, MockQuerier>, creator: &str| -> Addr {
+                from_binary(
+                    &query(
+                        deps.as_ref(),
+                        env.clone(),
+                        QueryMsg::PredictCampaignAddress {
+                            creator: creator.to_string(),
+                            salt: salt.clone(),
+                        },
+                    )
+                    .unwrap(),
+                )
+                .unwrap()
+            };
+        let predicted_user_1 = predict(&deps, USER_1);
+        let predicted_user_2 = predict(&deps, USER_2);
+
+        // factory instantiates campaign code with the salt of creator, as its admin
+        let factory = deps
+            .api
+            .addr_canonicalize(env.contract.address.as_str())
+            .unwrap();
+        let checksum = HexBinary::from_hex(CHECKSUM).unwrap();
+        let mut created = vec![];
+        for creator in [USER_1, USER_2] {
+            match create_salted_campaign(&mut deps, &env, creator, &salt) {
+                WasmMsg::Instantiate2 {
+                    admin,
+                    code_id,
+                    salt: creator_salt,
+                    ..
+                } => {
+                    assert_eq!(admin, Some(env.contract.address.to_string()));
+                    assert_eq!(code_id, CAMPAIGN_CODE_ID);
+                    let campaign_addr =
+                        instantiate2_address(checksum.as_slice(), &factory, &creator_salt).unwrap();
+                    created.push(deps.api.addr_humanize(&campaign_addr).unwrap());
+                }
+                msg => panic!("unexpected message {:?}", msg),
+            }
+        }
+
+        // campaign is instantiated at predicted address
+        assert_eq!(
+            created,
+            vec![predicted_user_1.clone(), predicted_user_2.clone()]
+        );
+
+        // same salt of another creator gives another address
+        assert_ne!(predicted_user_1, predicted_user_2);
+    }
+}
@@ -1,20 +1,40 @@
+use std::cmp::max;
+use std::collections::BTreeSet;
+
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, QueryRequest,
-    Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal256, Deps, DepsMut,
+    Empty, Env, MessageInfo, Order, QuerierWrapper, QueryRequest, Reply, ReplyOn, Response,
+    StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakeChangedHookMsg};
+use crate::reward::{
+    load_nft, move_nft_reward, staker_pending_reward, start_nft_reward, stop_nft_reward,
+    take_nft_reward, take_reward_remainder, update_pending_reward, OverlayStorage,
+};
 use crate::state::{
-    AssetToken, CampaignInfo, CampaignInfoResult, NftInfo, NftStake, StakedInfoResult,
-    StakerRewardAssetInfo, TokenInfo, CAMPAIGN_INFO, NFTS, STAKERS_INFO, TOKEN_IDS,
+    nfts, update_nft, AssetToken, CampaignInfo, CampaignInfoResult, EmissionScheduleResult,
+    HooksResponse, LoyaltyStep, NftInfo, NftStake, RewardMode, SimulateStakeResult,
+    SimulatedNftResult, SolvencyResult, StakedInfoResult, StakerInfoResult, StakerRewardAssetInfo,
+    TokenInfo, TotalPowerAtHeightResponse, UnallocatedRewardResult, VotingPowerAtHeightResponse,
+    CAMPAIGN_INFO, CURRENT_STAKERS, FACTORY, HOOKS, STAKED_POWER, STAKERS_INFO, TOTAL_NFT_STAKED,
+    TOTAL_POWER, TOTAL_STAKERS, UNALLOCATED_REWARD,
+};
+use crate::utils::{
+    add_reward, calc_protocol_fee, emission_phases, sub_reward, BASE_MULTIPLIER,
+    MAX_PROTOCOL_FEE_BPS,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg, NumTokensResponse, OwnerOfResponse};
+use cw721_base::{
+    ExecuteMsg as Cw721BaseExecuteMsg, Extension, InstantiateMsg as Cw721InstantiateMsg, MintMsg,
 };
-use crate::utils::{add_reward, calc_reward_in_time, sub_reward};
-use cw20::Cw20ExecuteMsg;
-use cw721::{Cw721ExecuteMsg, Cw721QueryMsg};
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, parse_reply_instantiate_data};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:campaign";
@@ -23,24 +43,44 @@ const MAX_TIME_VALID: u64 = 94608000; // 3 years
 const MAX_LENGTH_NAME: usize = 100;
 const MAX_LENGTH_IMAGE: usize = 500;
 const MAX_LENGTH_DESCRIPTION: usize = 500;
+const MAX_LOYALTY_STEPS: usize = 10;
+const MAX_EMISSION_PHASES: usize = 20;
+// settings for pagination
+const MAX_LIMIT: u32 = 100;
+const DEFAULT_LIMIT: u32 = 30;
+const SECONDS_PER_YEAR: u64 = 31536000; // 86400 * 365
+const SECONDS_PER_DAY: u64 = 86400;
+const INSTANTIATE_RECEIPT_REPLY_ID: u64 = 1;
+const HOOK_REPLY_ID: u64 = 2;
+// gas given to each hook, a hook running out of gas fails like any other error
+const HOOK_GAS_LIMIT: u64 = 500_000;
+// max receipts synced in a claim
+const RECEIPT_TOKENS_LIMIT: usize = 100;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     // set version to contract
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    // validate token contract address
-    match msg.reward_token_info.info.clone() {
+    // validate token contract address, native token can not be minted
+    match &msg.reward_token_info.info {
         TokenInfo::Token { contract_addr } => {
-            deps.api.addr_validate(&contract_addr)?;
+            deps.api
+                .addr_validate(contract_addr)
+                .map_err(ContractError::invalid_address(contract_addr))?;
         }
-        TokenInfo::NativeToken { denom: _ } => {
-            return Err(ContractError::InvalidToken {});
+        TokenInfo::NativeToken { denom } => {
+            if denom.is_empty() {
+                return Err(ContractError::InvalidToken {});
+            }
+            if matches!(msg.reward_mode, Some(RewardMode::Mint { .. })) {
+                return Err(ContractError::InvalidRewardMode {});
+            }
         }
     }
 
@@ -75,40 +115,148 @@ pub fn instantiate(
         });
     }
 
-    let total_percent = msg.lockup_term.iter().fold(Uint128::zero(), |acc, term| {
-        acc.checked_add(term.percent).unwrap()
-    });
+    let mut total_percent = Uint128::zero();
+    for term in msg.lockup_term.iter() {
+        total_percent = total_percent
+            .checked_add(term.percent)
+            .map_err(ContractError::overflow("sum lockup term percent"))?;
+    }
 
     if total_percent != Uint128::from(100u128) {
         return Err(ContractError::InvalidFunds {});
     }
 
+    // loyalty steps must be sorted by staked_time and multiplier can not decrease
+    let loyalty_steps = msg.loyalty_steps.clone().unwrap_or_default();
+    if loyalty_steps.len() > MAX_LOYALTY_STEPS {
+        return Err(ContractError::InvalidLoyaltyStep {});
+    }
+    let mut previous_step = LoyaltyStep {
+        staked_time: 0,
+        multiplier: Uint128::from(BASE_MULTIPLIER),
+    };
+    for step in loyalty_steps.iter() {
+        if step.staked_time <= previous_step.staked_time
+            || step.multiplier < previous_step.multiplier
+        {
+            return Err(ContractError::InvalidLoyaltyStep {});
+        }
+        previous_step = step.clone();
+    }
+
+    // emission schedule must start at start_time, phases sorted by start_time before end_time
+    let emission_schedule = msg.emission_schedule.clone().unwrap_or_default();
+    if !emission_schedule.is_empty()
+        && (emission_schedule.len() > MAX_EMISSION_PHASES
+            || emission_schedule[0].start_time != msg.start_time
+            || emission_schedule
+                .windows(2)
+                .any(|phases| phases[0].start_time >= phases[1].start_time)
+            || emission_schedule
+                .iter()
+                .any(|phase| phase.start_time >= msg.end_time)
+            || emission_schedule.iter().all(|phase| phase.weight.is_zero()))
+    {
+        return Err(ContractError::InvalidEmissionSchedule {});
+    }
+
+    // receipt of soft staked nft can not follow the nft kept in owner wallet
+    let soft_staking = msg.soft_staking.unwrap_or(false);
+    if soft_staking && msg.receipt_code_id.is_some() {
+        return Err(ContractError::InvalidStakingMode {});
+    }
+
+    // reward of mint mode is configured by reward_per_second instead of AddRewardToken
+    let reward_mode = msg.reward_mode.clone().unwrap_or_default();
+    let (total_reward, reward_per_second) = match &reward_mode {
+        RewardMode::Transfer => (Uint128::zero(), Uint128::zero()),
+        RewardMode::Mint {
+            reward_per_second, ..
+        } => {
+            if reward_per_second.is_zero() {
+                return Err(ContractError::InvalidRewardMode {});
+            }
+            let total_reward = reward_per_second
+                .checked_mul(Uint128::from(msg.end_time - msg.start_time))
+                .map_err(ContractError::overflow("calc total reward to mint"))?;
+            (total_reward, *reward_per_second)
+        }
+    };
+
+    // protocol fee is capped and needs a recipient
+    let protocol_fee_bps = msg.protocol_fee_bps.unwrap_or(0);
+    let protocol_fee_recipient = msg
+        .protocol_fee_recipient
+        .as_ref()
+        .map(|recipient| {
+            deps.api
+                .addr_validate(recipient)
+                .map_err(ContractError::invalid_address(recipient))
+        })
+        .transpose()?;
+    if protocol_fee_bps > MAX_PROTOCOL_FEE_BPS
+        || (protocol_fee_bps > 0 && protocol_fee_recipient.is_none())
+    {
+        return Err(ContractError::InvalidProtocolFee {});
+    }
+
     // campaign info
     let campaign = CampaignInfo {
-        owner: deps.api.addr_validate(&msg.owner).unwrap(),
+        owner: deps
+            .api
+            .addr_validate(&msg.owner)
+            .map_err(ContractError::invalid_address(&msg.owner))?,
         campaign_name: msg.campaign_name.clone(),
         campaign_image: msg.campaign_image.clone(),
         campaign_description: msg.campaign_description.clone(),
         total_reward_claimed: Uint128::zero(),
-        total_reward: Uint128::zero(),
+        total_reward,
         limit_per_staker: msg.limit_per_staker,
         reward_token: AssetToken {
             info: msg.reward_token_info.info.clone(),
             amount: Uint128::zero(),
         },
-        allowed_collection: deps.api.addr_validate(&msg.allowed_collection).unwrap(),
+        allowed_collection: deps
+            .api
+            .addr_validate(&msg.allowed_collection)
+            .map_err(ContractError::invalid_address(&msg.allowed_collection))?,
         lockup_term: msg.lockup_term.clone(),
-        reward_per_second: Uint128::zero(),
+        reward_per_second,
         time_calc_nft: 0,
         start_time: msg.start_time,
         end_time: msg.end_time,
+        distribution_mode: msg.distribution_mode.clone().unwrap_or_default(),
+        loyalty_steps,
+        emission_schedule,
+        rollover_unallocated: msg.rollover_unallocated.unwrap_or(false),
+        reward_dust: Uint128::zero(),
+        reward_dust_remainder: Decimal256::zero(),
+        receipt_collection: None,
+        reward_mode,
+        soft_staking,
+        voting_power_by_lockup: msg.voting_power_by_lockup.unwrap_or(false),
+        protocol_fee_bps,
+        protocol_fee_recipient,
     };
 
     // save campaign info
     CAMPAIGN_INFO.save(deps.storage, &campaign)?;
 
-    // init TOKEN_IDS to vec![]
-    TOKEN_IDS.save(deps.storage, &vec![])?;
+    // init TOTAL_NFT_STAKED to 0
+    TOTAL_NFT_STAKED.save(deps.storage, &0)?;
+
+    // init CURRENT_STAKERS and TOTAL_STAKERS to 0
+    CURRENT_STAKERS.save(deps.storage, &0)?;
+    TOTAL_STAKERS.save(deps.storage, &0)?;
+
+    // instantiator of campaign, usually the factory
+    FACTORY.save(deps.storage, &info.sender)?;
+
+    // init HOOKS to empty
+    HOOKS.save(deps.storage, &vec![])?;
+
+    // init TOTAL_POWER to 0
+    TOTAL_POWER.save(deps.storage, &Uint128::zero(), env.block.height)?;
 
     // we need emit the information of reward token to response
     let reward_token_info_str = match msg.reward_token_info.info {
@@ -116,8 +264,30 @@ pub fn instantiate(
         TokenInfo::NativeToken { denom } => denom,
     };
 
+    let mut res = Response::new();
+
+    // instantiate receipt collection, campaign is minter of receipts
+    if let Some(receipt_code_id) = msg.receipt_code_id {
+        res = res.add_submessage(SubMsg {
+            id: INSTANTIATE_RECEIPT_REPLY_ID,
+            gas_limit: None,
+            msg: CosmosMsg::Wasm(WasmMsg::Instantiate {
+                code_id: receipt_code_id,
+                funds: vec![],
+                admin: None,
+                label: "receipt".to_string(),
+                msg: to_binary(&Cw721InstantiateMsg {
+                    name: format!("{} receipt", msg.campaign_name),
+                    symbol: "RECEIPT".to_string(),
+                    minter: env.contract.address.to_string(),
+                })?,
+            }),
+            reply_on: ReplyOn::Success,
+        });
+    }
+
     // emit the information of instantiated campaign
-    Ok(Response::new().add_attributes([
+    Ok(res.add_attributes([
         ("action", "instantiate"),
         ("owner", &msg.owner),
         ("campaign_name", &msg.campaign_name),
@@ -129,9 +299,60 @@ pub fn instantiate(
         ("lockup_term", &format!("{:?}", &msg.lockup_term)),
         ("start_time", &msg.start_time.to_string()),
         ("end_time", &msg.end_time.to_string()),
+        (
+            "distribution_mode",
+            &format!("{:?}", campaign.distribution_mode),
+        ),
+        ("loyalty_steps", &format!("{:?}", campaign.loyalty_steps)),
+        (
+            "emission_schedule",
+            &format!("{:?}", campaign.emission_schedule),
+        ),
+        (
+            "rollover_unallocated",
+            &campaign.rollover_unallocated.to_string(),
+        ),
+        ("reward_mode", &format!("{:?}", campaign.reward_mode)),
+        ("soft_staking", &campaign.soft_staking.to_string()),
+        (
+            "voting_power_by_lockup",
+            &campaign.voting_power_by_lockup.to_string(),
+        ),
+        ("protocol_fee_bps", &campaign.protocol_fee_bps.to_string()),
     ]))
 }
 
+/// Stores the address of instantiated receipt collection, ignores failure of hooks
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_RECEIPT_REPLY_ID => {
+            let reply = parse_reply_instantiate_data(msg)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+            let receipt_collection = deps
+                .api
+                .addr_validate(&reply.contract_address)
+                .map_err(ContractError::invalid_address(&reply.contract_address))?;
+
+            CAMPAIGN_INFO.update(deps.storage, |mut campaign_info| -> StdResult<_> {
+                campaign_info.receipt_collection = Some(receipt_collection.clone());
+                Ok(campaign_info)
+            })?;
+
+            Ok(Response::new().add_attributes([
+                ("action", "reply_on_instantiate_receipt_success"),
+                ("receipt_collection", receipt_collection.as_str()),
+            ]))
+        }
+        // failing hook must not revert stake, unstake or claim, its state changes are reverted
+        HOOK_REPLY_ID => {
+            let error = msg.result.into_result().err().unwrap_or_default();
+            Ok(Response::new().add_attributes([("action", "hook_failed"), ("error", &error)]))
+        }
+        id => Err(StdError::generic_err(format!("Unknown reply id: {}", id)).into()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -143,8 +364,27 @@ pub fn execute(
         ExecuteMsg::AddRewardToken { amount } => execute_add_reward_token(deps, env, info, amount),
         ExecuteMsg::StakeNfts { nfts } => execute_stake_nft(deps, env, info, nfts),
         ExecuteMsg::UnStakeNft { token_id } => execute_unstake_nft(deps, env, info, token_id),
-        ExecuteMsg::ClaimReward { amount } => execute_claim_reward(deps, env, info, amount),
+        ExecuteMsg::ClaimReward {
+            amount,
+            receipt_ids,
+        } => execute_claim_reward(deps, env, info, amount, receipt_ids),
         ExecuteMsg::WithdrawReward {} => execute_withdraw_reward(deps, env, info),
+        ExecuteMsg::RenewNft {
+            token_id,
+            lockup_term,
+        } => execute_renew_nft(deps, env, info, token_id, lockup_term),
+        ExecuteMsg::ReclaimUnallocatedReward {} => {
+            execute_reclaim_unallocated_reward(deps, env, info)
+        }
+        ExecuteMsg::Reconcile {} => execute_reconcile(deps, env, info),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::AddHook { addr } => execute_add_hook(deps, info, addr),
+        ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, info, addr),
+        ExecuteMsg::Checkpoint { start_after, limit } => {
+            execute_checkpoint(deps, env, start_after, limit)
+        }
+        ExecuteMsg::Cancel {} => execute_cancel(deps, env, info),
     }
 }
 
@@ -159,17 +399,12 @@ pub fn execute_add_reward_token(
 
     let current_time = env.block.time.seconds();
 
-    // only owner can add reward token to campaign
-    if campaign_info.owner != info.sender {
+    // only owner or factory can add reward token to campaign
+    if campaign_info.owner != info.sender && FACTORY.load(deps.storage)? != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
-    // only reward_per_second == 0 || start_time > current_time can add reward
-    if campaign_info.reward_per_second != Uint128::zero()
-        && campaign_info.start_time <= current_time
-    {
-        return Err(ContractError::InvalidTimeToAddReward {});
-    }
+    check_add_reward(&campaign_info, current_time)?;
 
     let mut res = Response::new();
 
@@ -186,30 +421,27 @@ pub fn execute_add_reward_token(
                 })?,
                 funds: vec![],
             }));
-
-            // add token info to response
-            res = res.add_attribute("reward_token_info", contract_addr);
-
-            // update amount, reward_per_second token in campaign
-            campaign_info.reward_token.amount = campaign_info
-                .reward_token
-                .amount
-                .checked_add(amount)
-                .unwrap();
-            campaign_info.reward_per_second = campaign_info
-                .reward_token
-                .amount
-                .checked_div(Uint128::from(
-                    campaign_info.end_time - campaign_info.start_time,
-                ))
-                .unwrap();
-            campaign_info.total_reward = campaign_info.total_reward.checked_add(amount).unwrap();
-
-            // save campaign
-            CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
         }
-        TokenInfo::NativeToken { denom: _ } => {}
+        TokenInfo::NativeToken { denom } => {
+            // native reward is sent along with the message
+            if must_pay(&info, &denom)? != amount {
+                return Err(ContractError::InvalidFunds {});
+            }
+        }
     }
+
+    // add token info to response
+    res = res.add_attribute(
+        "reward_token_info",
+        campaign_info.reward_token.info.to_string(),
+    );
+
+    // update amount, reward_per_second token in campaign
+    deposit_reward(&mut campaign_info, amount)?;
+
+    // save campaign
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+
     Ok(res.add_attributes([
         ("action", "add_reward_token"),
         ("owner", campaign_info.owner.as_ref()),
@@ -217,19 +449,123 @@ pub fn execute_add_reward_token(
     ]))
 }
 
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // only reward token of campaign can be received
+    if campaign_info.reward_token.info
+        != (TokenInfo::Token {
+            contract_addr: info.sender.to_string(),
+        })
+    {
+        return Err(ContractError::InvalidToken {});
+    }
+
+    match from_binary(&msg.msg)? {
+        Cw20HookMsg::AddRewardToken {} => {
+            // only owner or factory can add reward token to campaign
+            let sender = deps
+                .api
+                .addr_validate(&msg.sender)
+                .map_err(ContractError::invalid_address(&msg.sender))?;
+            if sender != campaign_info.owner && sender != FACTORY.load(deps.storage)? {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            check_add_reward(&campaign_info, env.block.time.seconds())?;
+
+            // reward token is already sent to campaign
+            deposit_reward(&mut campaign_info, msg.amount)?;
+            CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+
+            Ok(Response::new().add_attributes([
+                ("action", "add_reward_token"),
+                ("owner", campaign_info.owner.as_ref()),
+                ("sender", sender.as_ref()),
+                ("reward_token_info", info.sender.as_ref()),
+                ("reward_token_amount", &msg.amount.to_string()),
+            ]))
+        }
+    }
+}
+
+// reward can be added in transfer mode, before start or while no reward is distributed
+fn check_add_reward(campaign_info: &CampaignInfo, current_time: u64) -> Result<(), ContractError> {
+    // reward of mint mode is minted on claim
+    if campaign_info.reward_mode != RewardMode::Transfer {
+        return Err(ContractError::InvalidRewardMode {});
+    }
+
+    // only reward_per_second == 0 || start_time > current_time can add reward
+    if campaign_info.reward_per_second != Uint128::zero()
+        && campaign_info.start_time <= current_time
+    {
+        return Err(ContractError::InvalidTimeToAddReward {});
+    }
+
+    Ok(())
+}
+
+// add deposited reward to campaign, reward_per_second spreads all reward over campaign
+fn deposit_reward(campaign_info: &mut CampaignInfo, amount: Uint128) -> Result<(), ContractError> {
+    campaign_info.reward_token.amount = campaign_info
+        .reward_token
+        .amount
+        .checked_add(amount)
+        .map_err(ContractError::overflow("add reward token amount"))?;
+    let duration = Uint128::from(campaign_info.end_time)
+        .checked_sub(Uint128::from(campaign_info.start_time))
+        .map_err(ContractError::overflow("calc campaign duration"))?;
+    campaign_info.reward_per_second = campaign_info
+        .reward_token
+        .amount
+        .checked_div(duration)
+        .map_err(ContractError::divide_by_zero("calc reward per second"))?;
+    campaign_info.total_reward = campaign_info
+        .total_reward
+        .checked_add(amount)
+        .map_err(ContractError::overflow("add total reward"))?;
+
+    Ok(())
+}
+
 pub fn execute_stake_nft(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    nfts: Vec<NftStake>,
+    stake_nfts: Vec<NftStake>,
 ) -> Result<Response, ContractError> {
+    // an empty list must not create a staker
+    if stake_nfts.is_empty() {
+        return Err(ContractError::EmptyNftList {});
+    }
+
+    // each nft can be staked once, a repeated token_id would count its power twice
+    let mut token_ids = BTreeSet::new();
+    if let Some(nft) = stake_nfts
+        .iter()
+        .find(|nft| !token_ids.insert(nft.token_id.as_str()))
+    {
+        return Err(ContractError::DuplicateTokenId {
+            token_id: nft.token_id.clone(),
+        });
+    }
+
     // load campaign info
     let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
     let current_time = env.block.time.seconds();
 
     // the reward token must be added to campaign before staking nft
-    if campaign_info.reward_token.amount == Uint128::zero() {
+    if campaign_info.reward_mode == RewardMode::Transfer
+        && campaign_info.reward_token.amount == Uint128::zero()
+    {
         return Err(ContractError::EmptyReward {});
     }
 
@@ -242,19 +578,21 @@ pub fn execute_stake_nft(
     }
 
     // load staker_info or default if staker has not staked nft
-    let mut staker_info = STAKERS_INFO
-        .may_load(deps.storage, info.sender.clone())?
-        .unwrap_or(StakerRewardAssetInfo {
-            token_ids: vec![],
-            reward_debt: Uint128::zero(),
-            reward_claimed: Uint128::zero(),
-        });
+    let prev_staker_info = STAKERS_INFO.may_load(deps.storage, info.sender.clone())?;
+    let is_new_staker = prev_staker_info.is_none();
+    let mut staker_info = prev_staker_info.unwrap_or(StakerRewardAssetInfo {
+        nft_count: 0,
+        reward_debt: Uint128::zero(),
+        reward_claimed: Uint128::zero(),
+        protocol_fee_paid: Uint128::zero(),
+    });
+    let is_active_staker = staker_info.nft_count > 0;
 
     // if limit per staker > 0 then check amount nft staked
     // if limit_per_staker = 0, then no limit nft stake
     if campaign_info.limit_per_staker > 0 {
-        // the length of token_ids + length nft staked should be smaller than limit per staker
-        if nfts.len() + staker_info.token_ids.len() > campaign_info.limit_per_staker as usize {
+        // nft count of staker + length nft staked should be smaller than limit per staker
+        if stake_nfts.len() as u64 + staker_info.nft_count > campaign_info.limit_per_staker {
             return Err(ContractError::LimitPerStake {});
         }
     }
@@ -262,85 +600,11 @@ pub fn execute_stake_nft(
     // prepare response
     let mut res = Response::new();
 
-    // list token_ids
-    let mut token_ids = TOKEN_IDS.load(deps.storage)?;
-
-    // if nft is first stake then skip calc reward
-    if campaign_info.time_calc_nft != 0 {
-        // update pending reward for previous staking nft
-        let terms = campaign_info.clone().lockup_term;
-        let reward_per_second = campaign_info.reward_per_second;
-
-        // load nfts
-        let mut nfts_load = Vec::new();
-        let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-        for item in nfts_storage {
-            let (_, nft_info) = item?;
-
-            // skip nft is end reward
-            if !nft_info.is_end_reward {
-                nfts_load.push(nft_info);
-            }
-        }
-
-        // filter nft by term
-        for term in terms {
-            let mut nft_list = nfts_load
-                .clone()
-                .into_iter()
-                .filter(|nft| nft.lockup_term.value == term.value)
-                .collect::<Vec<_>>();
-            nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-            let mut time_calc: u64 = campaign_info.time_calc_nft;
-            let mut nft_count = nft_list.len() as u128;
-            let mut reward = Uint128::zero();
-            for nft in nft_list.iter_mut() {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap();
-                }
-                // save nfts
-                NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
-            }
-        }
-    }
+    // update pending reward for previous staking nft
+    update_pending_reward(deps.storage, env.block.time.seconds(), &mut campaign_info)?;
 
     // check the owner of token_ids, all token_ids should be owned by info.sender
-    for nft in &nfts {
+    for nft in &stake_nfts {
         // let campaign_info = CAMPAIGN_INFO.load(deps.storage)?;
         // check invalid lockup_term
         if !campaign_info
@@ -352,6 +616,11 @@ pub fn execute_stake_nft(
             return Err(ContractError::InvalidLockupTerm {});
         }
 
+        // staked nft can not be staked again, soft staked nft is still in owner wallet
+        if nfts().has(deps.storage, nft.token_id.clone()) {
+            return Err(ContractError::AlreadyExist {});
+        }
+
         // check owner of nft
         let query_owner_msg = Cw721QueryMsg::OwnerOf {
             token_id: nft.token_id.clone(),
@@ -394,7 +663,7 @@ pub fn execute_stake_nft(
             .iter()
             .find(|&term| term.value == nft.lockup_term)
             .cloned()
-            .unwrap();
+            .ok_or(ContractError::InvalidLockupTerm {})?;
 
         let nft_info = NftInfo {
             token_id: nft.token_id.clone(),
@@ -405,26 +674,70 @@ pub fn execute_stake_nft(
             start_time: current_time,
             end_time: (current_time + lockup_term.value),
         };
-        // save info nft
-        NFTS.save(deps.storage, nft.token_id.clone(), &nft_info)?;
+        // save info nft, nft starts accruing reward
+        nfts().save(deps.storage, nft.token_id.clone(), &nft_info)?;
+        start_nft_reward(deps.storage, &mut campaign_info, &nft_info)?;
 
         // save staker_info
-        staker_info.token_ids.push(nft.token_id.clone());
-
-        token_ids.push(nft.token_id.clone());
+        staker_info.nft_count += 1;
+
+        // add voting power of nft to staker
+        add_voting_power(
+            deps.storage,
+            env.block.height,
+            &info.sender,
+            nft_voting_power(&campaign_info, &nft_info),
+        )?;
+
+        // soft staked nft is only registered
+        if !campaign_info.soft_staking {
+            res = res.add_message(transfer_nft_msg);
+        }
 
-        res = res.add_message(transfer_nft_msg);
+        // notify hooks of staked nft
+        res = res.add_submessages(hook_msgs(
+            deps.storage,
+            StakeChangedHookMsg::Stake {
+                collection: campaign_info.allowed_collection.clone(),
+                token_id: nft.token_id.clone(),
+                owner: info.sender.clone(),
+                lockup_term: nft_info.lockup_term.value,
+                amount: nft_voting_power(&campaign_info, &nft_info),
+            },
+        )?);
+
+        // mint receipt of staked nft to staker
+        if let Some(receipt_collection) = &campaign_info.receipt_collection {
+            res = res.add_message(WasmMsg::Execute {
+                contract_addr: receipt_collection.to_string(),
+                msg: to_binary(&Cw721BaseExecuteMsg::<Extension, Empty>::Mint(MintMsg {
+                    token_id: nft.token_id.clone(),
+                    owner: info.sender.to_string(),
+                    token_uri: None,
+                    extension: None,
+                }))?,
+                funds: vec![],
+            });
+        }
     }
 
     STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker_info)?;
 
-    // update time calc pending reward for nft
-    // let mut update_campaign = campaign_info.clone();
-    campaign_info.time_calc_nft = current_time;
+    // save campaign info with updated time calc pending reward
     CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
 
-    // save TOKEN_IDS
-    TOKEN_IDS.save(deps.storage, &token_ids)?;
+    // update TOTAL_NFT_STAKED
+    TOTAL_NFT_STAKED.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total + stake_nfts.len() as u64)
+    })?;
+
+    // update stakers counters
+    if !is_active_staker && !stake_nfts.is_empty() {
+        CURRENT_STAKERS.update(deps.storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+    }
+    if is_new_staker {
+        TOTAL_STAKERS.update(deps.storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+    }
 
     Ok(res.add_attributes([
         ("action", "stake_nft"),
@@ -433,7 +746,7 @@ pub fn execute_stake_nft(
             "allowed_collection",
             campaign_info.allowed_collection.as_ref(),
         ),
-        ("nfts", &format!("{:?}", &nfts)),
+        ("nfts", &format!("{:?}", &stake_nfts)),
     ]))
 }
 
@@ -442,133 +755,126 @@ pub fn execute_unstake_nft(
     env: Env,
     info: MessageInfo,
     token_id: String,
+) -> Result<Response, ContractError> {
+    // position of nft with receipt is unstaked by sending its receipt
+    if CAMPAIGN_INFO
+        .load(deps.storage)?
+        .receipt_collection
+        .is_some()
+    {
+        return Err(ContractError::UnstakeByReceipt {});
+    }
+
+    unstake_nft(deps, env, info.sender, token_id)
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // only receipt of this campaign can be received
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    if campaign_info.receipt_collection != Some(info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // holder sent the receipt, so the position is unstaked for holder
+    let holder = deps
+        .api
+        .addr_validate(&msg.sender)
+        .map_err(ContractError::invalid_address(&msg.sender))?;
+    let res = unstake_nft(deps, env, holder, msg.token_id.clone())?;
+
+    // burn receipt of unstaked nft
+    Ok(res.add_message(WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Burn {
+            token_id: msg.token_id,
+        })?,
+        funds: vec![],
+    }))
+}
+
+fn unstake_nft(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    token_id: String,
 ) -> Result<Response, ContractError> {
     // load campaign info
     let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
     // prepare response
     let mut res = Response::new();
 
-    if NFTS.may_load(deps.storage, token_id.clone())?.is_none() {
+    if nfts().may_load(deps.storage, token_id.clone())?.is_none() {
         return Err(ContractError::EmptyNft { token_id });
     }
 
-    // max time calc pending reward is campaign_info.end_time
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
-    }
-
     // update pending reward for previous staking nft
-    let terms = campaign_info.clone().lockup_term;
-    let reward_per_second = campaign_info.reward_per_second;
-
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        if !nft_info.is_end_reward {
-            nfts_load.push(nft_info);
-        }
-    }
-
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-        let mut time_calc: u64 = campaign_info.time_calc_nft;
-        let mut nft_count = nft_list.len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if nft.end_time <= current_time {
-                // calc in time_calc -> nft.end_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    nft.end_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
-
-                // increase reward for next nft
-                reward = add_reward(reward, calc_reward).unwrap();
-
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                time_calc = nft.end_time; // update time_calc
-                nft.is_end_reward = true; // nft stake timeout
-            } else {
-                // calc in time_calc -> current_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    current_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
-
-                // add reward previous and current reward
-                let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap();
-            }
-            if env.block.time.seconds() >= campaign_info.end_time {
-                nft.is_end_reward = true;
-            }
-            // save nfts
-            NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
-        }
+    update_pending_reward(deps.storage, env.block.time.seconds(), &mut campaign_info)?;
+
+    // position of nft with receipt follows the receipt holder, with its reward
+    let mut nft_info = nfts().load(deps.storage, token_id.clone())?;
+    if campaign_info.receipt_collection.is_some() && nft_info.owner != owner {
+        res = res.add_submessages(move_nft_position(
+            deps.storage,
+            env.block.height,
+            &mut campaign_info,
+            &mut nft_info,
+            &owner,
+        )?);
     }
 
-    // update time calc pending reward for nft
-    campaign_info.time_calc_nft = current_time;
-    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
-
-    // load nft info
-    let nft_info = NFTS.load(deps.storage, token_id.clone())?;
+    // load nft info with its reward
+    let mut nft_info = load_nft(deps.storage, &mut campaign_info, &token_id)?;
 
     // check time unstake and owner nft
+    if nft_info.owner != owner {
+        return Err(ContractError::NotOwner { token_id });
+    }
     if !nft_info.is_end_reward {
         return Err(ContractError::InvalidTimeToUnStake {});
     }
 
-    // prepare message to transfer nft back to the owner
-    let transfer_nft_msg = WasmMsg::Execute {
-        contract_addr: campaign_info.allowed_collection.to_string(),
-        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
-            recipient: info.sender.to_string(),
-            token_id: token_id.clone(),
-        })?,
-        funds: vec![],
-    };
-
-    // remove nft in NFTS
-    NFTS.remove(deps.storage, token_id.clone());
+    // remove nft in nfts, reward of nft is moved to staker
+    remove_staked_nft(
+        deps.storage,
+        env.block.height,
+        &mut campaign_info,
+        &mut nft_info,
+    )?;
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
 
-    // remove token_id in TOKEN_IDS
-    let mut token_ids = TOKEN_IDS.load(deps.storage)?;
-    token_ids.retain(|id| *id != token_id.clone());
-    TOKEN_IDS.save(deps.storage, &token_ids)?;
+    // transfer nft back to the owner, soft staked nft is already in owner wallet
+    if !campaign_info.soft_staking {
+        res = res.add_message(WasmMsg::Execute {
+            contract_addr: campaign_info.allowed_collection.to_string(),
+            msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: owner.to_string(),
+                token_id: token_id.clone(),
+            })?,
+            funds: vec![],
+        });
+    }
 
-    // update reward for staker
-    let mut staker = STAKERS_INFO.load(deps.storage, info.sender.clone())?;
-    staker.reward_debt = add_reward(staker.reward_debt, nft_info.pending_reward).unwrap();
-    staker.token_ids.retain(|key| *key != token_id.clone()); // remove nft for staker
-    STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker)?;
+    // notify hooks of unstaked nft
+    res = res.add_submessages(hook_msgs(
+        deps.storage,
+        StakeChangedHookMsg::Unstake {
+            collection: campaign_info.allowed_collection.clone(),
+            token_id: token_id.clone(),
+            owner: owner.clone(),
+            lockup_term: nft_info.lockup_term.value,
+            amount: nft_voting_power(&campaign_info, &nft_info),
+            reward: nft_info.pending_reward,
+        },
+    )?);
 
-    res = res.add_message(transfer_nft_msg);
     Ok(res.add_attributes([
         ("action", "unstake_nft"),
-        ("owner", info.sender.as_ref()),
+        ("owner", owner.as_ref()),
         (
             "allowed_collection",
             campaign_info.allowed_collection.as_ref(),
@@ -577,158 +883,283 @@ pub fn execute_unstake_nft(
     ]))
 }
 
-pub fn execute_claim_reward(
+pub fn execute_renew_nft(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    amount: Uint128,
+    token_id: String,
+    lockup_term: u64,
 ) -> Result<Response, ContractError> {
     // load campaign info
     let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
-    // Only stakers could claim rewards in this campaign
-    if STAKERS_INFO
-        .may_load(deps.storage, info.sender.clone())?
-        .is_none()
-    {
-        return Err(ContractError::InvalidClaim {});
-    }
+    let current_time = env.block.time.seconds();
 
-    // load staker_info
-    let mut staker_info = STAKERS_INFO.load(deps.storage, info.sender.clone())?;
+    // only current_time < end_time can renew nft
+    if campaign_info.end_time <= current_time {
+        return Err(ContractError::InvalidTimeToStakeNft {});
+    }
 
-    // max time calc pending reward is campaign_info.end_time
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
+    if nfts().may_load(deps.storage, token_id.clone())?.is_none() {
+        return Err(ContractError::EmptyNft { token_id });
     }
 
+    // load lockup_term in campaign info
+    let lockup_term = campaign_info
+        .lockup_term
+        .iter()
+        .find(|&term| term.value == lockup_term)
+        .cloned()
+        .ok_or(ContractError::InvalidLockupTerm {})?;
+
     // update pending reward for previous staking nft
-    let terms = campaign_info.clone().lockup_term;
-    let reward_per_second = campaign_info.reward_per_second;
-
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        if !nft_info.is_end_reward {
-            nfts_load.push(nft_info);
-        }
+    update_pending_reward(deps.storage, env.block.time.seconds(), &mut campaign_info)?;
+
+    // position of nft with receipt follows the receipt holder, with its reward
+    let mut nft_info = nfts().load(deps.storage, token_id.clone())?;
+    let hook_submsgs = sync_receipt_owner(
+        deps.storage,
+        &deps.querier,
+        env.block.height,
+        &mut campaign_info,
+        &mut nft_info,
+    )?;
+
+    // load nft info with its reward
+    let mut nft_info = load_nft(deps.storage, &mut campaign_info, &token_id)?;
+
+    // soft staked nft must still be in owner wallet
+    if campaign_info.soft_staking && !is_nft_owner(&deps.querier, &campaign_info, &nft_info)? {
+        return Err(ContractError::NotOwner { token_id });
     }
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-        let mut time_calc: u64 = campaign_info.time_calc_nft;
-        let mut nft_count = nft_list.len() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if nft.end_time <= current_time {
-                // calc in time_calc -> nft.end_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    nft.end_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
-
-                // increase reward for next nft
-                reward = add_reward(reward, calc_reward).unwrap();
-
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                time_calc = nft.end_time; // update time_calc
-                nft.is_end_reward = true; // nft stake timeout
-            } else {
-                // calc in time_calc -> current_time
-                let calc_reward = calc_reward_in_time(
-                    time_calc,
-                    current_time,
-                    reward_per_second,
-                    term.percent,
-                    nft_count,
-                )
-                .unwrap();
+    // check owner nft
+    if nft_info.owner != info.sender {
+        return Err(ContractError::NotOwner { token_id });
+    }
+
+    // only nft out of its lockup term can be renewed
+    if !nft_info.is_end_reward {
+        return Err(ContractError::InvalidTimeToRenew {});
+    }
 
-                // add reward previous and current reward
-                let accumulate_reward = add_reward(reward, calc_reward).unwrap();
+    // voting power of nft follows its new lockup term
+    sub_voting_power(
+        deps.storage,
+        env.block.height,
+        &nft_info.owner,
+        nft_voting_power(&campaign_info, &nft_info),
+    )?;
+
+    // nft start new lockup term, start_time is kept to count loyalty
+    nft_info.lockup_term = lockup_term.clone();
+    nft_info.is_end_reward = false;
+    nft_info.end_time = current_time + lockup_term.value;
+    nfts().save(deps.storage, token_id.clone(), &nft_info)?;
+    start_nft_reward(deps.storage, &mut campaign_info, &nft_info)?;
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
 
-                // update reward for nft
-                nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap();
-            }
+    add_voting_power(
+        deps.storage,
+        env.block.height,
+        &nft_info.owner,
+        nft_voting_power(&campaign_info, &nft_info),
+    )?;
+
+    Ok(Response::new()
+        .add_submessages(hook_submsgs)
+        .add_attributes([
+            ("action", "renew_nft"),
+            ("owner", info.sender.as_ref()),
+            ("token_id", &token_id),
+            ("lockup_term", &lockup_term.to_string()),
+            ("end_time", &nft_info.end_time.to_string()),
+        ]))
+}
+
+pub fn execute_claim_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    receipt_ids: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let receipt_ids = receipt_ids.unwrap_or_default();
+    if receipt_ids.len() > RECEIPT_TOKENS_LIMIT {
+        return Err(ContractError::TooManyTokenIds {});
+    }
+
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // update pending reward for previous staking nft
+    update_pending_reward(deps.storage, env.block.time.seconds(), &mut campaign_info)?;
+
+    let mut res = Response::new();
 
-            // if campaign is timeout -> nft timeout
-            if env.block.time.seconds() >= campaign_info.end_time {
-                nft.is_end_reward = true;
+    // positions of receipts transferred from staker or listed by staker follow the receipt holder
+    if campaign_info.receipt_collection.is_some() {
+        let mut token_ids = nfts()
+            .idx
+            .owner
+            .prefix(info.sender.clone())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        token_ids.extend(receipt_ids);
+        for id in token_ids {
+            // reward of nft is credited to its receipt holder
+            if let Some(mut nft) = nfts().may_load(deps.storage, id)? {
+                res = res.add_submessages(sync_receipt_owner(
+                    deps.storage,
+                    &deps.querier,
+                    env.block.height,
+                    &mut campaign_info,
+                    &mut nft,
+                )?);
             }
-            // save nfts
-            NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
         }
     }
 
-    // update time calc pending reward for nft
-    campaign_info.time_calc_nft = current_time;
+    // soft staked nfts of staker which are transferred stop accruing reward
+    if campaign_info.soft_staking {
+        let staked_nfts = nfts()
+            .idx
+            .owner
+            .prefix(info.sender.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, nft)| nft))
+            .collect::<StdResult<Vec<_>>>()?;
+        let (_, hook_submsgs) = release_transferred_nfts(
+            deps.storage,
+            &deps.querier,
+            env.block.height,
+            &mut campaign_info,
+            staked_nfts,
+        )?;
+        res = res.add_submessages(hook_submsgs);
+    }
+
+    // Only stakers could claim rewards in this campaign
+    if STAKERS_INFO
+        .may_load(deps.storage, info.sender.clone())?
+        .is_none()
+    {
+        return Err(ContractError::InvalidClaim {});
+    }
+
+    // load staker_info
+    let mut staker_info = STAKERS_INFO.load(deps.storage, info.sender.clone())?;
 
     // transfer pending reward in nft to staker
-    for id in staker_info.token_ids.iter() {
-        let mut nft = NFTS.load(deps.storage, id.clone())?;
-        staker_info.reward_debt = add_reward(staker_info.reward_debt, nft.pending_reward).unwrap();
+    let token_ids = nfts()
+        .idx
+        .owner
+        .prefix(info.sender.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for id in token_ids.iter() {
+        let mut nft = load_nft(deps.storage, &mut campaign_info, id)?;
+        take_nft_reward(deps.storage, &nft)?;
+        staker_info.reward_debt = add_reward(staker_info.reward_debt, nft.pending_reward)
+            .map_err(ContractError::overflow("move nft reward to staker"))?;
 
         //update pending reward for nft = 0 because pending reward in nft are transferred to staker
         nft.pending_reward = Uint128::zero();
-        NFTS.save(deps.storage, id.clone(), &nft)?;
+        nfts().save(deps.storage, id.clone(), &nft)?;
     }
 
+    // rounding remainder of reward credited to nfts of staker
+    let remainder = take_reward_remainder(deps.storage, &info.sender)?;
+    staker_info.reward_debt = add_reward(staker_info.reward_debt, remainder)
+        .map_err(ContractError::overflow("move reward remainder to staker"))?;
+
     // amount reward claim must be less than or equal reward in staker
     if amount > staker_info.reward_debt {
         return Err(ContractError::InsufficientBalance {});
     }
 
-    let mut res = Response::new();
-
-    match campaign_info.reward_token.info.clone() {
-        TokenInfo::Token { contract_addr } => {
-            // execute cw20 transfer msg from info.sender to contract
-            res = res.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: contract_addr.to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: info.sender.to_string(),
-                    amount,
-                })?,
-                funds: vec![],
-            }));
+    // total reward minted can not exceed mint cap
+    if let RewardMode::Mint {
+        mint_cap: Some(mint_cap),
+        ..
+    } = campaign_info.reward_mode
+    {
+        let total_minted = add_reward(campaign_info.total_reward_claimed, amount)
+            .map_err(ContractError::overflow("add total reward minted"))?;
+        if total_minted > mint_cap {
+            return Err(ContractError::ExceedMintCap {});
+        }
+    }
 
-            res = res.add_attributes([
-                ("reward_token_info", contract_addr),
-                ("reward_claim_amount", amount.to_string()),
-            ]);
+    // protocol fee is taken from claimed amount
+    let protocol_fee = calc_protocol_fee(amount, campaign_info.protocol_fee_bps);
+    let net_amount = sub_reward(amount, protocol_fee).map_err(ContractError::overflow(
+        "sub protocol fee from claimed reward",
+    ))?;
+
+    // transfer reward from contract to recipient, or mint cw20 reward in mint mode
+    let reward_msg = |recipient: &Addr, amount: Uint128| -> StdResult<CosmosMsg> {
+        match (&campaign_info.reward_mode, &campaign_info.reward_token.info) {
+            (RewardMode::Mint { .. }, TokenInfo::Token { contract_addr }) => {
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Mint {
+                        recipient: recipient.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                }))
+            }
+            _ => transfer_reward_msg(&campaign_info.reward_token.info, recipient, amount),
+        }
+    };
+    res = res.add_message(reward_msg(&info.sender, net_amount)?);
+    if let Some(recipient) = &campaign_info.protocol_fee_recipient {
+        if !protocol_fee.is_zero() {
+            res = res.add_message(reward_msg(recipient, protocol_fee)?);
+        }
+    }
 
-            // update staker info
-            staker_info.reward_claimed = add_reward(staker_info.reward_claimed, amount).unwrap();
-            staker_info.reward_debt = sub_reward(staker_info.reward_debt, amount).unwrap();
-            STAKERS_INFO.save(deps.storage, info.sender, &staker_info)?;
+    res = res.add_attributes([
+        (
+            "reward_token_info",
+            campaign_info.reward_token.info.to_string(),
+        ),
+        ("reward_claim_amount", amount.to_string()),
+        ("protocol_fee_amount", protocol_fee.to_string()),
+        ("net_reward_amount", net_amount.to_string()),
+    ]);
+
+    // update staker info
+    staker_info.reward_claimed = add_reward(staker_info.reward_claimed, amount)
+        .map_err(ContractError::overflow("add staker reward claimed"))?;
+    staker_info.protocol_fee_paid = add_reward(staker_info.protocol_fee_paid, protocol_fee)
+        .map_err(ContractError::overflow("add staker protocol fee paid"))?;
+    staker_info.reward_debt = sub_reward(staker_info.reward_debt, amount)
+        .map_err(ContractError::overflow("sub staker reward debt"))?;
+    STAKERS_INFO.save(deps.storage, info.sender.clone(), &staker_info)?;
 
-            // update reward total and reward claimed for campaign
-            campaign_info.reward_token.amount =
-                sub_reward(campaign_info.reward_token.amount, amount).unwrap();
-            campaign_info.total_reward_claimed =
-                add_reward(campaign_info.total_reward_claimed, amount).unwrap();
+    // notify hooks of claimed reward
+    res = res.add_submessages(hook_msgs(
+        deps.storage,
+        StakeChangedHookMsg::Claim {
+            collection: campaign_info.allowed_collection.clone(),
+            owner: info.sender,
+            amount,
+        },
+    )?);
 
-            // save campaign info
-            CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
-        }
-        TokenInfo::NativeToken { denom: _ } => {}
+    // update reward total and reward claimed for campaign, minted reward is not in balance
+    if campaign_info.reward_mode == RewardMode::Transfer {
+        campaign_info.reward_token.amount =
+            sub_reward(campaign_info.reward_token.amount, amount)
+                .map_err(ContractError::overflow("sub claimed reward from campaign"))?;
     }
+    campaign_info.total_reward_claimed = add_reward(campaign_info.total_reward_claimed, amount)
+        .map_err(ContractError::overflow("add total reward claimed"))?;
+
+    // save campaign info
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
     Ok(res.add_attributes([
         ("action", "claim_reward"),
         ("owner", campaign_info.owner.as_ref()),
@@ -753,158 +1184,655 @@ pub fn execute_withdraw_reward(
         return Err(ContractError::InvalidTimeToWithdrawReward {});
     }
 
+    // campaign of mint mode has no reward balance to withdraw
+    if campaign_info.reward_mode != RewardMode::Transfer {
+        return Ok(Response::new().add_attributes([
+            ("action", "withdraw_reward"),
+            ("owner", campaign_info.owner.as_ref()),
+            ("withdraw_reward_amount", "0"),
+        ]));
+    }
+
+    // update pending reward for all nft, campaign is ended so all nfts are timeout
+    update_pending_reward(deps.storage, env.block.time.seconds(), &mut campaign_info)?;
+
     // total_pending_reward = total reward in nfts + total reward in stakers
     let mut total_pending_reward = Uint128::zero();
+    let stakers_info = STAKERS_INFO.range(deps.storage, None, None, Order::Ascending);
+    for item in stakers_info {
+        let (owner, staker) = item?;
+        let pending_reward = staker_pending_reward(deps.storage, &owner)?;
+        total_pending_reward = add_reward(total_pending_reward, staker.reward_debt)
+            .and_then(|total| total.checked_add(pending_reward))
+            .map_err(ContractError::overflow("sum pending reward of stakers"))?;
+    }
+
+    // reward remaining = reward in campaign - total pending reward
+    let withdraw_reward = campaign_info
+        .reward_token
+        .amount
+        .checked_sub(total_pending_reward)
+        .map_err(ContractError::overflow("calc remaining reward"))?;
 
-    // time to calc pending reward
-    let current_time = campaign_info.end_time;
+    // transfer reward from contract to owner
+    let mut res = Response::new().add_message(transfer_reward_msg(
+        &campaign_info.reward_token.info,
+        &info.sender,
+        withdraw_reward,
+    )?);
 
-    // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let reward_per_second = campaign_info.reward_per_second;
+    // remaining reward includes unallocated reward and rounding dust
+    res = res.add_attributes([
+        (
+            "reward_token_info",
+            campaign_info.reward_token.info.to_string(),
+        ),
+        ("withdraw_reward_amount", withdraw_reward.to_string()),
+        ("reward_dust", campaign_info.reward_dust.to_string()),
+    ]);
+
+    // update reward total and reward claimed for campaign
+    campaign_info.reward_token.amount =
+        sub_reward(campaign_info.reward_token.amount, withdraw_reward).map_err(
+            ContractError::overflow("sub withdrawn reward from campaign"),
+        )?;
+    campaign_info.reward_dust = Uint128::zero();
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        if !nft_info.is_end_reward {
-            nfts_load.push(nft_info);
-        }
+    // unallocated reward is withdrawn with remaining reward
+    UNALLOCATED_REWARD.clear(deps.storage);
+
+    Ok(res.add_attributes([
+        ("action", "withdraw_reward"),
+        ("owner", campaign_info.owner.as_ref()),
+    ]))
+}
+
+pub fn execute_reclaim_unallocated_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // permission check
+    if info.sender != campaign_info.owner {
+        return Err(ContractError::Unauthorized {});
     }
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
+    // unallocated reward of mint mode is never minted
+    if campaign_info.reward_mode != RewardMode::Transfer {
+        return Err(ContractError::InvalidRewardMode {});
+    }
 
-        let mut time_calc: u64 = campaign_info.time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .count() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-                nft.is_end_reward = true;
-            }
+    // update pending reward for all nft, unallocated reward is updated to current time
+    update_pending_reward(deps.storage, env.block.time.seconds(), &mut campaign_info)?;
 
-            // pending reward in nft
-            total_pending_reward = add_reward(total_pending_reward, nft.pending_reward).unwrap();
+    let mut reclaim_reward = Uint128::zero();
+    let unallocated_rewards = UNALLOCATED_REWARD
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, amount) in unallocated_rewards {
+        reclaim_reward = add_reward(reclaim_reward, amount)
+            .map_err(ContractError::overflow("sum unallocated reward"))?;
+    }
 
-            // save nfts
-            NFTS.save(deps.storage, nft.token_id.clone(), nft)?;
-        }
+    if reclaim_reward.is_zero() {
+        return Err(ContractError::EmptyReward {});
     }
 
-    // pending reward in staker
-    let stakers_info = STAKERS_INFO.range(deps.storage, None, None, Order::Ascending);
-    for item in stakers_info {
-        let (_, value) = item?;
-        total_pending_reward = add_reward(total_pending_reward, value.reward_debt).unwrap();
+    // transfer reward from contract to owner
+    let mut res = Response::new().add_message(transfer_reward_msg(
+        &campaign_info.reward_token.info,
+        &info.sender,
+        reclaim_reward,
+    )?);
+
+    res = res.add_attributes([
+        (
+            "reward_token_info",
+            campaign_info.reward_token.info.to_string(),
+        ),
+        ("reclaim_reward_amount", reclaim_reward.to_string()),
+    ]);
+
+    // update reward in campaign
+    campaign_info.reward_token.amount =
+        sub_reward(campaign_info.reward_token.amount, reclaim_reward).map_err(
+            ContractError::overflow("sub reclaimed reward from campaign"),
+        )?;
+    UNALLOCATED_REWARD.clear(deps.storage);
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+
+    Ok(res.add_attributes([
+        ("action", "reclaim_unallocated_reward"),
+        ("owner", campaign_info.owner.as_ref()),
+    ]))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // campaign is cancelled through factory, which keeps its status
+    if info.sender != FACTORY.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // nft is staked only after start, so no reward is owed to stakers
+    let current_time = env.block.time.seconds();
+    if campaign_info.start_time <= current_time {
+        return Err(ContractError::InvalidTimeToUpdate {});
     }
 
-    // update time calc pending reward for nft
-    campaign_info.time_calc_nft = current_time;
+    // campaign ends now, its reward can be withdrawn by owner. Emission phases all start
+    // after the new end time, so none is kept.
+    campaign_info.start_time = current_time;
+    campaign_info.end_time = current_time;
+    campaign_info.emission_schedule.clear();
     CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
 
-    // reward remaining = reward in campaign - total pending reward
-    let withdraw_reward = campaign_info
-        .reward_token
-        .amount
-        .checked_sub(total_pending_reward)
-        .unwrap();
+    Ok(Response::new().add_attributes([
+        ("action", "cancel"),
+        ("end_time", &current_time.to_string()),
+    ]))
+}
 
-    let mut res = Response::new();
-    match campaign_info.reward_token.info.clone() {
-        TokenInfo::Token { contract_addr } => {
-            // execute cw20 transfer msg from info.sender to contract
+pub fn execute_reconcile(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
-            res = res.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: contract_addr.to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: info.sender.to_string(),
-                    amount: withdraw_reward,
-                })?,
-                funds: vec![],
-            }));
+    // permission check
+    if info.sender != campaign_info.owner {
+        return Err(ContractError::Unauthorized {});
+    }
 
-            res = res.add_attributes([
-                ("reward_token_info", contract_addr),
-                ("withdraw_reward_amount", withdraw_reward.to_string()),
-            ]);
+    // surplus = actual balance - recorded balance, staker liabilities are kept in recorded balance
+    let actual_balance = query_reward_balance(deps.as_ref(), &env, &campaign_info)?;
+    let surplus = actual_balance.saturating_sub(campaign_info.reward_token.amount);
 
-            // update reward total and reward claimed for campaign
-            campaign_info.reward_token.amount =
-                sub_reward(campaign_info.reward_token.amount, withdraw_reward).unwrap();
-            CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
-        }
-        TokenInfo::NativeToken { denom: _ } => {}
+    if surplus.is_zero() {
+        return Err(ContractError::EmptySurplus {});
     }
+
+    // transfer reward from contract to owner
+    let mut res = Response::new().add_message(transfer_reward_msg(
+        &campaign_info.reward_token.info,
+        &info.sender,
+        surplus,
+    )?);
+
+    res = res.add_attributes([
+        (
+            "reward_token_info",
+            campaign_info.reward_token.info.to_string(),
+        ),
+        ("surplus_amount", surplus.to_string()),
+    ]);
+
     Ok(res.add_attributes([
-        ("action", "withdraw_reward"),
+        ("action", "reconcile"),
         ("owner", campaign_info.owner.as_ref()),
     ]))
 }
 
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // permission check
+    if info.sender != campaign_info.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = deps
+        .api
+        .addr_validate(&addr)
+        .map_err(ContractError::invalid_address(&addr))?;
+
+    let mut hooks = HOOKS.load(deps.storage)?;
+    if hooks.contains(&hook) {
+        return Err(ContractError::HookAlreadyRegistered {});
+    }
+    hooks.push(hook.clone());
+    HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new().add_attributes([
+        ("action", "add_hook"),
+        ("owner", campaign_info.owner.as_ref()),
+        ("hook", hook.as_ref()),
+    ]))
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // permission check
+    if info.sender != campaign_info.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = deps
+        .api
+        .addr_validate(&addr)
+        .map_err(ContractError::invalid_address(&addr))?;
+
+    let mut hooks = HOOKS.load(deps.storage)?;
+    if !hooks.contains(&hook) {
+        return Err(ContractError::HookNotRegistered {});
+    }
+    hooks.retain(|registered| registered != hook);
+    HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new().add_attributes([
+        ("action", "remove_hook"),
+        ("owner", campaign_info.owner.as_ref()),
+        ("hook", hook.as_ref()),
+    ]))
+}
+
+pub fn execute_checkpoint(
+    deps: DepsMut,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    // load campaign info
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // only soft staked nfts are kept in owner wallet
+    if !campaign_info.soft_staking {
+        return Err(ContractError::InvalidStakingMode {});
+    }
+
+    // update pending reward for all nft, transferred nfts get reward to this checkpoint
+    update_pending_reward(deps.storage, env.block.time.seconds(), &mut campaign_info)?;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let staked_nfts = nfts()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, nft)| nft))
+        .collect::<StdResult<Vec<_>>>()?;
+    let last_token_id = staked_nfts.last().map(|nft| nft.token_id.clone());
+
+    let (released, hook_submsgs) = release_transferred_nfts(
+        deps.storage,
+        &deps.querier,
+        env.block.height,
+        &mut campaign_info,
+        staked_nfts,
+    )?;
+    CAMPAIGN_INFO.save(deps.storage, &campaign_info)?;
+
+    Ok(Response::new()
+        .add_submessages(hook_submsgs)
+        .add_attributes([
+            ("action", "checkpoint"),
+            ("last_token_id", &last_token_id.unwrap_or_default()),
+            ("released_nfts", &format!("{:?}", released)),
+        ]))
+}
+
+// remove staked nft, pending reward of nft is moved to its staker
+fn remove_staked_nft(
+    storage: &mut dyn Storage,
+    height: u64,
+    campaign_info: &mut CampaignInfo,
+    nft_info: &mut NftInfo,
+) -> Result<(), ContractError> {
+    // nft stops accruing reward, remove nft in nfts
+    stop_nft_reward(storage, campaign_info, nft_info)?;
+    take_nft_reward(storage, nft_info)?;
+    nfts().remove(storage, nft_info.token_id.clone())?;
+
+    // remove voting power of nft from staker
+    sub_voting_power(
+        storage,
+        height,
+        &nft_info.owner,
+        nft_voting_power(campaign_info, nft_info),
+    )?;
+
+    // update TOTAL_NFT_STAKED
+    TOTAL_NFT_STAKED.update(storage, |total| -> StdResult<_> {
+        Ok(total.saturating_sub(1))
+    })?;
+
+    // update reward for staker
+    let mut staker = STAKERS_INFO.load(storage, nft_info.owner.clone())?;
+    staker.reward_debt = add_reward(staker.reward_debt, nft_info.pending_reward)
+        .map_err(ContractError::overflow("move nft reward to staker"))?;
+    staker.nft_count = staker.nft_count.saturating_sub(1); // remove nft for staker
+    STAKERS_INFO.save(storage, nft_info.owner.clone(), &staker)?;
+
+    // staker has no nft staked anymore
+    if staker.nft_count == 0 {
+        CURRENT_STAKERS.update(storage, |total| -> StdResult<_> {
+            Ok(total.saturating_sub(1))
+        })?;
+    }
+
+    Ok(())
+}
+
+// soft staking: remove staked nfts which are not owned by their staker anymore,
+// reward accrued until now is kept for staker. Returns token ids of removed nfts
+// and the unstake messages to hooks.
+fn release_transferred_nfts(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    height: u64,
+    campaign_info: &mut CampaignInfo,
+    staked_nfts: Vec<NftInfo>,
+) -> Result<(Vec<String>, Vec<SubMsg>), ContractError> {
+    let mut released = Vec::new();
+    let mut hook_submsgs = Vec::new();
+    for mut nft in staked_nfts {
+        if !is_nft_owner(querier, campaign_info, &nft)? {
+            remove_staked_nft(storage, height, campaign_info, &mut nft)?;
+            hook_submsgs.extend(hook_msgs(
+                storage,
+                StakeChangedHookMsg::Unstake {
+                    collection: campaign_info.allowed_collection.clone(),
+                    token_id: nft.token_id.clone(),
+                    owner: nft.owner.clone(),
+                    lockup_term: nft.lockup_term.value,
+                    amount: nft_voting_power(campaign_info, &nft),
+                    reward: nft.pending_reward,
+                },
+            )?);
+            released.push(nft.token_id);
+        }
+    }
+
+    Ok((released, hook_submsgs))
+}
+
+// staker of nft still owns it in allowed collection, burned nft is not owned by anyone.
+// OwnerOf of burned nft fails while the collection still answers NumTokens, any other
+// failure of collection is returned, it must not release the nft.
+fn is_nft_owner(
+    querier: &QuerierWrapper,
+    campaign_info: &CampaignInfo,
+    nft: &NftInfo,
+) -> StdResult<bool> {
+    match querier.query_wasm_smart::<OwnerOfResponse>(
+        &campaign_info.allowed_collection,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: nft.token_id.clone(),
+            include_expired: Some(false),
+        },
+    ) {
+        Ok(res) => Ok(res.owner == nft.owner.as_str()),
+        Err(err @ StdError::ParseErr { .. }) => Err(err),
+        Err(err) => {
+            querier
+                .query_wasm_smart::<NumTokensResponse>(
+                    &campaign_info.allowed_collection,
+                    &Cw721QueryMsg::NumTokens {},
+                )
+                .map_err(|_| err)?;
+            Ok(false)
+        }
+    }
+}
+
+// move position of staked nft to new owner, unclaimed pending reward of nft goes with it.
+// Returns the unstake and stake messages to hooks.
+fn move_nft_position(
+    storage: &mut dyn Storage,
+    height: u64,
+    campaign_info: &mut CampaignInfo,
+    nft: &mut NftInfo,
+    new_owner: &Addr,
+) -> Result<Vec<SubMsg>, ContractError> {
+    // voting power of nft goes with it
+    let power = nft_voting_power(campaign_info, nft);
+    sub_voting_power(storage, height, &nft.owner, power)?;
+    add_voting_power(storage, height, new_owner, power)?;
+
+    // remove nft from previous owner, position is synced lazily so reward accrued since
+    // the transfer is not known, previous owner claims before transferring the receipt
+    let mut prev_staker = STAKERS_INFO.load(storage, nft.owner.clone())?;
+    prev_staker.nft_count = prev_staker.nft_count.saturating_sub(1);
+    STAKERS_INFO.save(storage, nft.owner.clone(), &prev_staker)?;
+    let mut hook_submsgs = hook_msgs(
+        storage,
+        StakeChangedHookMsg::Unstake {
+            collection: campaign_info.allowed_collection.clone(),
+            token_id: nft.token_id.clone(),
+            owner: nft.owner.clone(),
+            lockup_term: nft.lockup_term.value,
+            amount: power,
+            reward: Uint128::zero(),
+        },
+    )?;
+    if prev_staker.nft_count == 0 {
+        CURRENT_STAKERS.update(storage, |total| -> StdResult<_> {
+            Ok(total.saturating_sub(1))
+        })?;
+    }
+
+    // add nft to new owner
+    let new_staker = STAKERS_INFO.may_load(storage, new_owner.clone())?;
+    if new_staker.is_none() {
+        TOTAL_STAKERS.update(storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+    }
+    let mut new_staker = new_staker.unwrap_or(StakerRewardAssetInfo {
+        nft_count: 0,
+        reward_debt: Uint128::zero(),
+        reward_claimed: Uint128::zero(),
+        protocol_fee_paid: Uint128::zero(),
+    });
+    if new_staker.nft_count == 0 {
+        CURRENT_STAKERS.update(storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+    }
+    new_staker.nft_count += 1;
+    STAKERS_INFO.save(storage, new_owner.clone(), &new_staker)?;
+
+    let prev_nft = nfts().load(storage, nft.token_id.clone())?;
+    move_nft_reward(storage, campaign_info, nft, new_owner)?;
+    update_nft(storage, &prev_nft, nft)?;
+
+    hook_submsgs.extend(hook_msgs(
+        storage,
+        StakeChangedHookMsg::Stake {
+            collection: campaign_info.allowed_collection.clone(),
+            token_id: nft.token_id.clone(),
+            owner: new_owner.clone(),
+            lockup_term: nft.lockup_term.value,
+            amount: power,
+        },
+    )?);
+
+    Ok(hook_submsgs)
+}
+
+// move position of nft to holder of its receipt, if campaign mints receipts.
+// Returns the messages to hooks of moved position.
+fn sync_receipt_owner(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    height: u64,
+    campaign_info: &mut CampaignInfo,
+    nft: &mut NftInfo,
+) -> Result<Vec<SubMsg>, ContractError> {
+    if let Some(receipt_collection) = campaign_info.receipt_collection.clone() {
+        let receipt: OwnerOfResponse = querier.query_wasm_smart(
+            receipt_collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: nft.token_id.clone(),
+                include_expired: Some(false),
+            },
+        )?;
+        let holder = Addr::unchecked(receipt.owner);
+        if holder != nft.owner {
+            return move_nft_position(storage, height, campaign_info, nft, &holder);
+        }
+    }
+
+    Ok(vec![])
+}
+
+// message to every registered hook, error of a failing hook is caught in reply
+fn hook_msgs(storage: &dyn Storage, msg: StakeChangedHookMsg) -> StdResult<Vec<SubMsg>> {
+    HOOKS
+        .load(storage)?
+        .iter()
+        .map(|hook| {
+            Ok(SubMsg {
+                id: HOOK_REPLY_ID,
+                gas_limit: Some(HOOK_GAS_LIMIT),
+                msg: msg.clone().into_cosmos_msg(hook)?,
+                reply_on: ReplyOn::Error,
+            })
+        })
+        .collect()
+}
+
+// voting power of staked nft, 1 or whole days of its lockup term (at least 1) if weighted by lockup
+fn nft_voting_power(campaign_info: &CampaignInfo, nft: &NftInfo) -> Uint128 {
+    if campaign_info.voting_power_by_lockup {
+        Uint128::from(max(nft.lockup_term.value / SECONDS_PER_DAY, 1))
+    } else {
+        Uint128::one()
+    }
+}
+
+fn add_voting_power(
+    storage: &mut dyn Storage,
+    height: u64,
+    staker: &Addr,
+    power: Uint128,
+) -> Result<(), ContractError> {
+    STAKED_POWER.update(storage, staker, height, |staked_power| {
+        staked_power
+            .unwrap_or_default()
+            .checked_add(power)
+            .map_err(ContractError::overflow("add voting power of staker"))
+    })?;
+    TOTAL_POWER.update(storage, height, |total_power| {
+        total_power
+            .unwrap_or_default()
+            .checked_add(power)
+            .map_err(ContractError::overflow("add total voting power"))
+    })?;
+
+    Ok(())
+}
+
+fn sub_voting_power(
+    storage: &mut dyn Storage,
+    height: u64,
+    staker: &Addr,
+    power: Uint128,
+) -> Result<(), ContractError> {
+    STAKED_POWER.update(storage, staker, height, |staked_power| {
+        staked_power
+            .unwrap_or_default()
+            .checked_sub(power)
+            .map_err(ContractError::overflow("sub voting power of staker"))
+    })?;
+    TOTAL_POWER.update(storage, height, |total_power| {
+        total_power
+            .unwrap_or_default()
+            .checked_sub(power)
+            .map_err(ContractError::overflow("sub total voting power"))
+    })?;
+
+    Ok(())
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::CampaignInfo {} => Ok(to_binary(&query_campaign_info(deps)?)?),
-        QueryMsg::NftInfo { token_id } => Ok(to_binary(&query_nft_info(deps, env, token_id)?)?),
+        QueryMsg::NftInfo { token_id, at_time } => {
+            Ok(to_binary(&query_nft_info(deps, env, token_id, at_time)?)?)
+        }
         QueryMsg::Nft { token_id } => Ok(to_binary(&query_nft(deps, env, token_id)?)?),
-        QueryMsg::NftStaked { owner } => Ok(to_binary(&query_staker_info(deps, env, owner)?)?),
-        QueryMsg::Nfts { limit } => Ok(to_binary(&query_nfts(deps, env, limit)?)?),
-        QueryMsg::TotalPendingReward {} => Ok(to_binary(&query_total_pending_reward(deps, env)?)?),
-        QueryMsg::TokenIds {} => Ok(to_binary(&query_token_ids(deps)?)?),
+        QueryMsg::NftStaked {
+            owner,
+            start_after,
+            limit,
+            at_time,
+        } => Ok(to_binary(&query_staker_info(
+            deps,
+            env,
+            owner,
+            start_after,
+            limit,
+            at_time,
+        )?)?),
+        QueryMsg::Nfts {
+            lockup_term,
+            start_after,
+            limit,
+            at_time,
+        } => Ok(to_binary(&query_nfts(
+            deps,
+            env,
+            lockup_term,
+            start_after,
+            limit,
+            at_time,
+        )?)?),
+        QueryMsg::Stakers { start_after, limit } => {
+            Ok(to_binary(&query_stakers(deps, env, start_after, limit)?)?)
+        }
+        QueryMsg::TotalPendingReward { at_time } => {
+            Ok(to_binary(&query_total_pending_reward(deps, env, at_time)?)?)
+        }
+        QueryMsg::TokenIds { start_after, limit } => {
+            Ok(to_binary(&query_token_ids(deps, start_after, limit)?)?)
+        }
+        QueryMsg::EmissionSchedule {} => Ok(to_binary(&query_emission_schedule(deps, env)?)?),
+        QueryMsg::UnallocatedReward {} => Ok(to_binary(&query_unallocated_reward(deps, env)?)?),
+        QueryMsg::VotingPowerAtHeight { address, height } => Ok(to_binary(
+            &query_voting_power_at_height(deps, env, address, height)?,
+        )?),
+        QueryMsg::TotalPowerAtHeight { height } => {
+            Ok(to_binary(&query_total_power_at_height(deps, env, height)?)?)
+        }
+        QueryMsg::Hooks {} => Ok(to_binary(&query_hooks(deps)?)?),
+        QueryMsg::Solvency {} => Ok(to_binary(&query_solvency(deps, env)?)?),
+        QueryMsg::SimulateStake {
+            nfts,
+            staker,
+            at_time,
+        } => Ok(to_binary(&query_simulate_stake(
+            deps, env, nfts, staker, at_time,
+        )?)?),
     }
 }
 
 fn query_campaign_info(deps: Deps) -> Result<CampaignInfoResult, ContractError> {
     let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
-    let total_nft_staked = TOKEN_IDS.load(deps.storage)?.len() as u64;
+    let total_nft_staked = TOTAL_NFT_STAKED.load(deps.storage)?;
+    let current_stakers = CURRENT_STAKERS.load(deps.storage)?;
+    let total_stakers = TOTAL_STAKERS.load(deps.storage)?;
 
     let campaign_result = CampaignInfoResult {
         owner: campaign_info.owner,
@@ -922,395 +1850,498 @@ fn query_campaign_info(deps: Deps) -> Result<CampaignInfoResult, ContractError>
         reward_per_second: campaign_info.reward_per_second,
         time_calc_nft: campaign_info.time_calc_nft,
         total_nft_staked,
+        current_stakers,
+        total_stakers,
+        distribution_mode: campaign_info.distribution_mode,
+        loyalty_steps: campaign_info.loyalty_steps,
+        emission_schedule: campaign_info.emission_schedule,
+        rollover_unallocated: campaign_info.rollover_unallocated,
+        reward_dust: campaign_info.reward_dust,
+        receipt_collection: campaign_info.receipt_collection,
+        reward_mode: campaign_info.reward_mode,
+        soft_staking: campaign_info.soft_staking,
+        voting_power_by_lockup: campaign_info.voting_power_by_lockup,
+        protocol_fee_bps: campaign_info.protocol_fee_bps,
+        protocol_fee_recipient: campaign_info.protocol_fee_recipient,
     };
     Ok(campaign_result)
 }
 
-fn query_nft_info(deps: Deps, env: Env, token_id: String) -> Result<NftInfo, ContractError> {
-    let mut info: NftInfo = NFTS.load(deps.storage, token_id)?;
-
-    // if nft is active then calculate
-    if !info.is_end_reward {
-        // get time to calc pending reward
-        let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
-        let mut current_time = env.block.time.seconds();
-        if campaign_info.end_time < env.block.time.seconds() {
-            current_time = campaign_info.end_time;
-        }
-        // update pending reward for all nft
-        let terms = campaign_info.clone().lockup_term;
-        let reward_per_second = campaign_info.reward_per_second;
-
-        // load nfts
-        let mut nfts_load = Vec::new();
-        let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-        for item in nfts_storage {
-            let (_, nft_info) = item?;
-            if !nft_info.is_end_reward {
-                nfts_load.push(nft_info);
-            }
-        }
-
-        for term in terms {
-            let mut nft_list = nfts_load
-                .clone()
-                .into_iter()
-                .filter(|nft| nft.lockup_term.value == term.value)
-                .collect::<Vec<_>>();
-            nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-            let mut time_calc: u64 = campaign_info.time_calc_nft;
-            let mut nft_count = nft_list
-                .clone()
-                .into_iter()
-                .filter(|nft| !nft.is_end_reward)
-                .count() as u128;
-            let mut reward = Uint128::zero();
-            for nft in nft_list.iter_mut() {
-                if !nft.is_end_reward {
-                    if nft.end_time <= current_time {
-                        // calc in time_calc -> nft.end_time
-                        let calc_reward = calc_reward_in_time(
-                            time_calc,
-                            nft.end_time,
-                            reward_per_second,
-                            term.percent,
-                            nft_count,
-                        )
-                        .unwrap();
-
-                        // increase reward for next nft
-                        reward = add_reward(reward, calc_reward).unwrap();
-
-                        // update reward for nft
-                        nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                        nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                        time_calc = nft.end_time; // update time_calc
-                        nft.is_end_reward = true; // nft stake timeout
-                    } else {
-                        // calc in time_calc -> current_time
-                        let calc_reward = calc_reward_in_time(
-                            time_calc,
-                            current_time,
-                            reward_per_second,
-                            term.percent,
-                            nft_count,
-                        )
-                        .unwrap();
-
-                        // add reward previous and current reward
-                        let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                        // update reward for nft
-                        nft.pending_reward =
-                            add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                    }
-                    if env.block.time.seconds() >= campaign_info.end_time {
-                        nft.is_end_reward = true;
-                    }
-                }
-                if info.token_id == nft.token_id {
-                    info = nft.clone();
-                }
-            }
-        }
-    }
+fn query_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    at_time: Option<u64>,
+) -> Result<NftInfo, ContractError> {
+    let query_time = query_time(&env, at_time)?;
 
-    Ok(info)
+    // update pending reward to query time without saving
+    let (mut storage, mut campaign_info) = reward_storage(deps, query_time)?;
+    load_nft(&mut storage, &mut campaign_info, &token_id)
 }
 
 fn query_nft(deps: Deps, _env: Env, token_id: String) -> Result<NftInfo, ContractError> {
-    let info: NftInfo = NFTS.load(deps.storage, token_id)?;
+    let info: NftInfo = nfts().load(deps.storage, token_id)?;
 
     Ok(info)
 }
 
-fn query_staker_info(deps: Deps, env: Env, owner: Addr) -> Result<StakedInfoResult, ContractError> {
+fn query_staker_info(
+    deps: Deps,
+    env: Env,
+    owner: Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    at_time: Option<u64>,
+) -> Result<StakedInfoResult, ContractError> {
+    let query_time = query_time(&env, at_time)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
     let staker_asset: StakerRewardAssetInfo = STAKERS_INFO
-        .may_load(deps.storage, owner)?
+        .may_load(deps.storage, owner.clone())?
         .unwrap_or(StakerRewardAssetInfo {
-            token_ids: vec![],
+            nft_count: 0,
             reward_debt: Uint128::zero(),
             reward_claimed: Uint128::zero(),
+            protocol_fee_paid: Uint128::zero(),
         });
 
     let mut staked_info = StakedInfoResult {
         nfts: vec![],
         reward_debt: staker_asset.reward_debt,
         reward_claimed: staker_asset.reward_claimed,
+        protocol_fee_paid: staker_asset.protocol_fee_paid,
+        net_reward_claimed: sub_reward(staker_asset.reward_claimed, staker_asset.protocol_fee_paid)
+            .map_err(ContractError::overflow(
+                "sub protocol fee from reward claimed",
+            ))?,
     };
 
-    // get time to calc pending reward
-    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
+    // update pending reward to query time without saving
+    let (mut storage, mut campaign_info) = reward_storage(deps, query_time)?;
+
+    // reward debt includes pending reward of all nfts of staker
+    staked_info.reward_debt = add_reward(
+        staked_info.reward_debt,
+        staker_pending_reward(&storage, &owner)?,
+    )
+    .map_err(ContractError::overflow("sum pending reward of staker"))?;
+
+    // only nfts of the page are credited
+    let start = start_after.map(Bound::exclusive);
+    let token_ids = nfts()
+        .idx
+        .owner
+        .prefix(owner)
+        .keys(&storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for token_id in token_ids {
+        staked_info
+            .nfts
+            .push(load_nft(&mut storage, &mut campaign_info, &token_id)?);
     }
 
-    // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let reward_per_second = campaign_info.reward_per_second;
+    Ok(staked_info)
+}
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        nfts_load.push(nft_info);
+fn query_nfts(
+    deps: Deps,
+    env: Env,
+    lockup_term: Option<u64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    at_time: Option<u64>,
+) -> Result<Vec<NftInfo>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    // update pending reward to query time without saving
+    let query_time = query_time(&env, at_time)?;
+    let (mut storage, mut campaign_info) = reward_storage(deps, query_time)?;
+
+    let token_ids = match lockup_term {
+        Some(lockup_term) => {
+            nfts()
+                .idx
+                .lockup_term
+                .prefix(lockup_term)
+                .keys(&storage, start, None, Order::Ascending)
+        }
+        None => nfts().keys(&storage, start, None, Order::Ascending),
     }
+    .take(limit)
+    .collect::<StdResult<Vec<_>>>()?;
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
-
-        let mut time_calc: u64 = campaign_info.time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .count() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-                if env.block.time.seconds() >= campaign_info.end_time {
-                    nft.is_end_reward = true;
-                }
-            }
-            if staker_asset.token_ids.contains(&nft.token_id) {
-                staked_info.nfts.push(nft.clone());
-                staked_info.reward_debt =
-                    add_reward(staked_info.reward_debt, nft.pending_reward).unwrap();
-            }
-        }
+    // only nfts of the page are credited
+    let mut result = Vec::with_capacity(token_ids.len());
+    for token_id in token_ids {
+        result.push(load_nft(&mut storage, &mut campaign_info, &token_id)?);
     }
 
-    Ok(staked_info)
+    Ok(result)
 }
 
-fn query_nfts(deps: Deps, env: Env, limit: Option<u32>) -> Result<Vec<NftInfo>, ContractError> {
-    let mut result_nfts = vec![];
+fn query_stakers(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<StakerInfoResult>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(start_after) => Some(Bound::exclusive(
+            deps.api
+                .addr_validate(&start_after)
+                .map_err(ContractError::invalid_address(&start_after))?,
+        )),
+        None => None,
+    };
 
-    let limit = limit.unwrap_or(30u32) as usize;
-    // get time to calc pending reward
-    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
-    }
-    // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let reward_per_second = campaign_info.reward_per_second;
+    // update pending reward to current time without saving
+    let (storage, _) = reward_storage(deps, env.block.time.seconds())?;
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        nfts_load.push(nft_info);
-    }
+    let stakers_info = STAKERS_INFO.range(deps.storage, start, None, Order::Ascending);
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
+    let mut result = Vec::new();
+    for item in stakers_info.take(limit) {
+        let (owner, staker) = item?;
 
-        let mut time_calc: u64 = campaign_info.time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .count() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-                if env.block.time.seconds() >= campaign_info.end_time {
-                    nft.is_end_reward = true;
-                }
-            }
-            result_nfts.push(nft.clone());
-        }
+        // add pending reward of nfts of staker
+        let reward_debt = add_reward(staker.reward_debt, staker_pending_reward(&storage, &owner)?)
+            .map_err(ContractError::overflow("sum pending reward of staker"))?;
+
+        result.push(StakerInfoResult {
+            owner,
+            nft_count: staker.nft_count,
+            reward_debt,
+            reward_claimed: staker.reward_claimed,
+        });
     }
-    result_nfts = result_nfts.iter().take(limit).cloned().collect::<Vec<_>>();
 
-    Ok(result_nfts)
+    Ok(result)
+}
+
+fn query_total_pending_reward(
+    deps: Deps,
+    env: Env,
+    at_time: Option<u64>,
+) -> Result<Uint128, ContractError> {
+    let query_time = query_time(&env, at_time)?;
+
+    calc_total_pending_reward(deps, query_time)
 }
 
-fn query_total_pending_reward(deps: Deps, env: Env) -> Result<Uint128, ContractError> {
+fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> Result<VotingPowerAtHeightResponse, ContractError> {
+    let address = deps
+        .api
+        .addr_validate(&address)
+        .map_err(ContractError::invalid_address(&address))?;
+    let height = height.unwrap_or(env.block.height);
+
+    let power = STAKED_POWER
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+
+    Ok(VotingPowerAtHeightResponse { power, height })
+}
+
+fn query_total_power_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> Result<TotalPowerAtHeightResponse, ContractError> {
+    let height = height.unwrap_or(env.block.height);
+
+    let power = TOTAL_POWER
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+
+    Ok(TotalPowerAtHeightResponse { power, height })
+}
+
+fn query_hooks(deps: Deps) -> Result<HooksResponse, ContractError> {
+    let hooks = HOOKS
+        .load(deps.storage)?
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    Ok(HooksResponse { hooks })
+}
+
+fn query_solvency(deps: Deps, env: Env) -> Result<SolvencyResult, ContractError> {
     let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
 
+    let total_liabilities = calc_total_pending_reward(deps, env.block.time.seconds())?;
+    let recorded_balance = campaign_info.reward_token.amount;
+    let actual_balance = query_reward_balance(deps, &env, &campaign_info)?;
+
+    Ok(SolvencyResult {
+        total_liabilities,
+        recorded_balance,
+        actual_balance,
+        surplus: actual_balance.saturating_sub(recorded_balance),
+        deficit: recorded_balance.saturating_sub(actual_balance),
+        is_solvent: actual_balance >= total_liabilities && recorded_balance >= total_liabilities,
+    })
+}
+
+// total = pending reward in nfts to query_time + reward debt of stakers
+fn calc_total_pending_reward(deps: Deps, query_time: u64) -> Result<Uint128, ContractError> {
     // total = pending in nft + pending in staker
     let mut total_pending_reward: Uint128 = Uint128::zero();
 
-    // max time to calc = campaign_info.end_time
-    let mut current_time = env.block.time.seconds();
-    if campaign_info.end_time < env.block.time.seconds() {
-        current_time = campaign_info.end_time;
+    // update pending reward to query time without saving
+    let (storage, _) = reward_storage(deps, query_time)?;
+
+    let stakers_info = STAKERS_INFO.range(deps.storage, None, None, Order::Ascending);
+    for item in stakers_info {
+        let (owner, staker) = item?;
+        let pending_reward = staker_pending_reward(&storage, &owner)?;
+        total_pending_reward = add_reward(total_pending_reward, staker.reward_debt)
+            .and_then(|total| total.checked_add(pending_reward))
+            .map_err(ContractError::overflow("sum pending reward of stakers"))?;
     }
-    // update pending reward for all nft
-    let terms = campaign_info.clone().lockup_term;
-    let reward_per_second = campaign_info.reward_per_second;
 
-    // load nfts
-    let mut nfts_load = Vec::new();
-    let nfts_storage = NFTS.range(deps.storage, None, None, Order::Ascending);
-    for item in nfts_storage {
-        let (_, nft_info) = item?;
-        nfts_load.push(nft_info);
+    Ok(total_pending_reward)
+}
+
+fn query_token_ids(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<String>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let token_ids = nfts()
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(token_ids)
+}
+
+fn query_emission_schedule(deps: Deps, env: Env) -> Result<EmissionScheduleResult, ContractError> {
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    let current_time = env.block.time.seconds();
+
+    // current and upcoming phases
+    let phases = emission_phases(&campaign_info)?
+        .into_iter()
+        .filter(|phase| phase.end_time > current_time)
+        .collect::<Vec<_>>();
+
+    let current_reward_per_second = phases
+        .iter()
+        .find(|phase| phase.start_time <= current_time)
+        .map(|phase| phase.reward_per_second)
+        .unwrap_or_default();
+
+    Ok(EmissionScheduleResult {
+        current_reward_per_second,
+        phases,
+    })
+}
+
+fn query_unallocated_reward(
+    deps: Deps,
+    env: Env,
+) -> Result<Vec<UnallocatedRewardResult>, ContractError> {
+    // unallocated reward is updated to current time without saving
+    let (storage, campaign_info) = reward_storage(deps, env.block.time.seconds())?;
+
+    let mut result = Vec::with_capacity(campaign_info.lockup_term.len());
+    for term in campaign_info.lockup_term.iter() {
+        result.push(UnallocatedRewardResult {
+            lockup_term: term.value,
+            amount: UNALLOCATED_REWARD
+                .may_load(&storage, term.value)?
+                .unwrap_or_default(),
+        });
     }
 
-    for term in terms {
-        let mut nft_list = nfts_load
-            .clone()
-            .into_iter()
-            .filter(|nft| nft.lockup_term.value == term.value)
-            .collect::<Vec<_>>();
-        nft_list.sort_by(|a, b| a.end_time.cmp(&b.end_time));
+    Ok(result)
+}
 
-        let mut time_calc: u64 = campaign_info.time_calc_nft;
-        let mut nft_count = nft_list
-            .clone()
-            .into_iter()
-            .filter(|nft| !nft.is_end_reward)
-            .count() as u128;
-        let mut reward = Uint128::zero();
-        for nft in nft_list.iter_mut() {
-            if !nft.is_end_reward {
-                if nft.end_time <= current_time {
-                    // calc in time_calc -> nft.end_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        nft.end_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // increase reward for next nft
-                    reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, reward).unwrap();
-
-                    nft_count = nft_count.checked_sub(1).unwrap(); // update count nft for next calc reward
-                    time_calc = nft.end_time; // update time_calc
-                    nft.is_end_reward = true; // nft stake timeout
-                } else {
-                    // calc in time_calc -> current_time
-                    let calc_reward = calc_reward_in_time(
-                        time_calc,
-                        current_time,
-                        reward_per_second,
-                        term.percent,
-                        nft_count,
-                    )
-                    .unwrap();
-
-                    // add reward previous and current reward
-                    let accumulate_reward = add_reward(reward, calc_reward).unwrap();
-
-                    // update reward for nft
-                    nft.pending_reward = add_reward(nft.pending_reward, accumulate_reward).unwrap()
-                }
-            }
-            // pending reward in nft
-            total_pending_reward = add_reward(total_pending_reward, nft.pending_reward).unwrap();
+fn query_simulate_stake(
+    deps: Deps,
+    env: Env,
+    stake_nfts: Vec<NftStake>,
+    staker: Option<String>,
+    at_time: Option<u64>,
+) -> Result<SimulateStakeResult, ContractError> {
+    let campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+
+    // nfts are staked now, or at start_time if campaign is not active yet
+    let stake_time = max(env.block.time.seconds(), campaign_info.start_time);
+    if stake_time >= campaign_info.end_time {
+        return Err(ContractError::InvalidTimeToStakeNft {});
+    }
+
+    // update pending reward to stake time without saving
+    let (mut storage, mut campaign_info) = reward_storage(deps, stake_time)?;
+
+    // simulated nfts share weight with active nfts of staker, or are owned by the campaign
+    let owner = match staker {
+        Some(staker) => deps
+            .api
+            .addr_validate(&staker)
+            .map_err(ContractError::invalid_address(&staker))?,
+        None => env.contract.address.clone(),
+    };
+    let mut simulate_nfts = Vec::with_capacity(stake_nfts.len());
+    for nft in stake_nfts.iter() {
+        let lockup_term = campaign_info
+            .lockup_term
+            .iter()
+            .find(|&term| term.value == nft.lockup_term)
+            .cloned()
+            .ok_or(ContractError::InvalidLockupTerm {})?;
+
+        // staked nft can not be staked again
+        if nfts().has(&storage, nft.token_id.clone()) {
+            return Err(ContractError::AlreadyExist {});
         }
+        let nft_info = NftInfo {
+            token_id: nft.token_id.clone(),
+            owner: owner.clone(),
+            pending_reward: Uint128::zero(),
+            lockup_term: lockup_term.clone(),
+            is_end_reward: false,
+            start_time: stake_time,
+            end_time: stake_time + lockup_term.value,
+        };
+        nfts().save(&mut storage, nft.token_id.clone(), &nft_info)?;
+        start_nft_reward(&mut storage, &mut campaign_info, &nft_info)?;
+        simulate_nfts.push(nft_info);
     }
-    // get pending reward in staker
-    let stakers_info = STAKERS_INFO.range(deps.storage, None, None, Order::Ascending);
-    for item in stakers_info {
-        let (_, value) = item?;
-        total_pending_reward = add_reward(total_pending_reward, value.reward_debt).unwrap();
+
+    // default simulation time is the end of the longest lockup term of simulated nfts
+    let at_time = at_time
+        .unwrap_or_else(|| {
+            stake_nfts
+                .iter()
+                .map(|nft| stake_time + nft.lockup_term)
+                .max()
+                .unwrap_or(stake_time)
+        })
+        .clamp(stake_time, campaign_info.end_time);
+
+    // annual reward of nft = its reward until end of its lockup term, scaled to a year
+    let rate_end_time = simulate_nfts
+        .iter()
+        .map(|nft| nft.end_time)
+        .max()
+        .unwrap_or(stake_time)
+        .min(campaign_info.end_time);
+    let mut rate_storage = storage.clone();
+    let mut rate_campaign_info = campaign_info.clone();
+    update_pending_reward(&mut rate_storage, rate_end_time, &mut rate_campaign_info)?;
+    let mut annual_rewards = Vec::with_capacity(simulate_nfts.len());
+    for nft in simulate_nfts.iter() {
+        let nft = load_nft(&mut rate_storage, &mut rate_campaign_info, &nft.token_id)?;
+        annual_rewards.push(
+            match nft.end_time.min(campaign_info.end_time) - stake_time {
+                0 => Uint128::zero(),
+                staked_time => nft
+                    .pending_reward
+                    .multiply_ratio(SECONDS_PER_YEAR, staked_time),
+            },
+        );
     }
 
-    Ok(total_pending_reward)
+    update_pending_reward(&mut storage, at_time, &mut campaign_info)?;
+
+    let mut result = SimulateStakeResult {
+        at_time,
+        nfts: Vec::with_capacity(simulate_nfts.len()),
+        total_projected_reward: Uint128::zero(),
+    };
+    for (nft, annual_reward) in simulate_nfts.iter().zip(annual_rewards) {
+        let nft = load_nft(&mut storage, &mut campaign_info, &nft.token_id)?;
+        result.total_projected_reward =
+            add_reward(result.total_projected_reward, nft.pending_reward)
+                .map_err(ContractError::overflow("sum projected reward"))?;
+        result.nfts.push(SimulatedNftResult {
+            token_id: nft.token_id,
+            lockup_term: nft.lockup_term.value,
+            start_time: nft.start_time,
+            end_time: nft.end_time,
+            projected_reward: nft.pending_reward,
+            annual_reward,
+        });
+    }
+
+    Ok(result)
 }
 
-fn query_token_ids(deps: Deps) -> Result<Vec<String>, ContractError> {
-    let token_ids = TOKEN_IDS.load(deps.storage)?;
+// transfer msg of reward token from campaign to recipient
+fn transfer_reward_msg(
+    reward_token: &TokenInfo,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    match reward_token {
+        TokenInfo::Token { contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })),
+        TokenInfo::NativeToken { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        })),
+    }
+}
 
-    Ok(token_ids)
+// balance of reward token held by campaign
+fn query_reward_balance(deps: Deps, env: &Env, campaign_info: &CampaignInfo) -> StdResult<Uint128> {
+    match &campaign_info.reward_token.info {
+        TokenInfo::Token { contract_addr } => {
+            let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                contract_addr,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            Ok(balance.balance)
+        }
+        TokenInfo::NativeToken { denom } => Ok(deps
+            .querier
+            .query_balance(&env.contract.address, denom)?
+            .amount),
+    }
+}
+
+// time to calculate pending reward in query, at_time must not be in the past
+fn query_time(env: &Env, at_time: Option<u64>) -> Result<u64, ContractError> {
+    let current_time = env.block.time.seconds();
+    match at_time {
+        Some(at_time) if at_time < current_time => Err(ContractError::InvalidQueryTime {}),
+        Some(at_time) => Ok(at_time),
+        None => Ok(current_time),
+    }
+}
+
+// storage and campaign info with pending reward updated to query_time, changes are not saved
+fn reward_storage(
+    deps: Deps,
+    query_time: u64,
+) -> Result<(OverlayStorage, CampaignInfo), ContractError> {
+    let mut storage = OverlayStorage::new(deps.storage);
+    let mut campaign_info: CampaignInfo = CAMPAIGN_INFO.load(deps.storage)?;
+    update_pending_reward(&mut storage, query_time, &mut campaign_info)?;
+
+    Ok((storage, campaign_info))
 }
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{ConversionOverflowError, DivideByZeroError, OverflowError, StdError};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("##Unauthorized##")]
     Unauthorized {},
 
@@ -30,6 +34,24 @@ pub enum ContractError {
     #[error("## Invalid LockupTerm ##")]
     InvalidLockupTerm {},
 
+    #[error("## Invalid LoyaltyStep ##")]
+    InvalidLoyaltyStep {},
+
+    #[error("## Invalid EmissionSchedule ##")]
+    InvalidEmissionSchedule {},
+
+    #[error("## Not supported in this staking mode ##")]
+    InvalidStakingMode {},
+
+    #[error("## Not supported in this reward mode ##")]
+    InvalidRewardMode {},
+
+    #[error("## Invalid protocol fee ##")]
+    InvalidProtocolFee {},
+
+    #[error("## Claim exceeds mint cap of reward ##")]
+    ExceedMintCap {},
+
     #[error("## Insufficient balance ##")]
     InsufficientBalance {},
 
@@ -45,6 +67,15 @@ pub enum ContractError {
     #[error("## This NFT is still in staking period. Cannot unstake now ##")]
     InvalidTimeToUnStake {},
 
+    #[error("## Send the receipt of this NFT to the campaign to unstake ##")]
+    UnstakeByReceipt {},
+
+    #[error("## This NFT is still in staking period. Cannot renew now ##")]
+    InvalidTimeToRenew {},
+
+    #[error("## Query time must not be before current time ##")]
+    InvalidQueryTime {},
+
     #[error("## Cannot deposit rewards to this pool ##")]
     InvalidTimeToAddReward {},
 
@@ -57,12 +88,82 @@ pub enum ContractError {
     #[error("## Invalid time to withdraw reward ##")]
     InvalidTimeToWithdrawReward {},
 
+    #[error("## Hook is already registered ##")]
+    HookAlreadyRegistered {},
+
+    #[error("## Hook is not registered ##")]
+    HookNotRegistered {},
+
     #[error("## Already exist ##")]
     AlreadyExist {},
 
     #[error("## Empty reward pool ##")]
     EmptyReward {},
 
+    #[error("## No surplus reward to reconcile ##")]
+    EmptySurplus {},
+
+    #[error("## No nft to stake ##")]
+    EmptyNftList {},
+
+    #[error("## Duplicate token_id: {token_id:?} ##")]
+    DuplicateTokenId { token_id: String },
+
     #[error("## Empty token_id: {token_id:?} ##")]
     EmptyNft { token_id: String },
+
+    #[error("## Overflow when {context}: {source} ##")]
+    Overflow {
+        context: String,
+        source: OverflowError,
+    },
+
+    #[error("## Divide by zero when {context}: {source} ##")]
+    DivideByZero {
+        context: String,
+        source: DivideByZeroError,
+    },
+
+    #[error("## Overflow when {context}: {source} ##")]
+    ConversionOverflow {
+        context: String,
+        source: ConversionOverflowError,
+    },
+
+    #[error("## Invalid address {address:?}: {source} ##")]
+    InvalidAddress { address: String, source: StdError },
+}
+
+impl ContractError {
+    /// Maps an overflow of checked arithmetic to `Overflow` with context
+    pub fn overflow(context: &str) -> impl FnOnce(OverflowError) -> Self + '_ {
+        move |source| ContractError::Overflow {
+            context: context.to_string(),
+            source,
+        }
+    }
+
+    /// Maps a division by zero of checked arithmetic to `DivideByZero` with context
+    pub fn divide_by_zero(context: &str) -> impl FnOnce(DivideByZeroError) -> Self + '_ {
+        move |source| ContractError::DivideByZero {
+            context: context.to_string(),
+            source,
+        }
+    }
+
+    /// Maps an overflow of a narrowing conversion to `ConversionOverflow` with context
+    pub fn conversion_overflow(context: &str) -> impl FnOnce(ConversionOverflowError) -> Self + '_ {
+        move |source| ContractError::ConversionOverflow {
+            context: context.to_string(),
+            source,
+        }
+    }
+
+    /// Maps a failed address validation to `InvalidAddress`
+    pub fn invalid_address(address: &str) -> impl FnOnce(StdError) -> Self + '_ {
+        move |source| ContractError::InvalidAddress {
+            address: address.to_string(),
+            source,
+        }
+    }
 }
@@ -1,6 +1,7 @@
 pub mod contract;
 mod error;
 pub mod msg;
+mod reward;
 pub mod state;
 pub mod utils;
 
@@ -1,7 +1,14 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
 
-use crate::state::{AssetToken, CampaignInfo, LockupTerm, NftInfo, NftStake, StakedInfoResult};
+use crate::state::{
+    AssetToken, CampaignInfo, DistributionMode, EmissionPhase, EmissionScheduleResult,
+    HooksResponse, LockupTerm, LoyaltyStep, NftInfo, NftStake, RewardMode, SimulateStakeResult,
+    SolvencyResult, StakedInfoResult, StakerInfoResult, TotalPowerAtHeightResponse,
+    UnallocatedRewardResult, VotingPowerAtHeightResponse,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -18,20 +25,75 @@ pub struct InstantiateMsg {
 
     pub start_time: u64, // start time must be from T + 1
     pub end_time: u64,   // max 3 years
+
+    pub distribution_mode: Option<DistributionMode>, // default Linear
+    pub loyalty_steps: Option<Vec<LoyaltyStep>>,     // default no loyalty multiplier
+    pub emission_schedule: Option<Vec<EmissionPhase>>, // default constant reward_per_second
+    pub rollover_unallocated: Option<bool>,          // default false
+    pub receipt_code_id: Option<u64>, // cw721 code to mint stake receipts, default no receipt
+    pub reward_mode: Option<RewardMode>, // default Transfer
+    pub soft_staking: Option<bool>,   // default false, nft is transferred to campaign
+    pub voting_power_by_lockup: Option<bool>, // default false, voting power of each nft is 1,
+    // otherwise whole days of its lockup term, at least 1
+    pub protocol_fee_bps: Option<u64>, // default 0, fee on claimed reward
+    pub protocol_fee_recipient: Option<String>, // required if protocol fee is set
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    AddRewardToken { amount: Uint128 },
+    AddRewardToken {
+        amount: Uint128,
+    },
     // user can stake 1 or many nfts to this campaign
-    StakeNfts { nfts: Vec<NftStake> },
+    StakeNfts {
+        nfts: Vec<NftStake>,
+    },
 
-    // user can claim reward
-    ClaimReward { amount: Uint128 },
+    // user can claim reward, receipt_ids are receipts bought by user, max 100,
+    // positions of nfts are moved to user with their unclaimed reward
+    ClaimReward {
+        amount: Uint128,
+        receipt_ids: Option<Vec<String>>,
+    },
 
     WithdrawReward {},
 
-    UnStakeNft { token_id: String },
+    UnStakeNft {
+        token_id: String,
+    },
+
+    // lock a staked nft again after its lockup term ended, loyalty time is kept
+    RenewNft {
+        token_id: String,
+        lockup_term: u64,
+    },
+    ReclaimUnallocatedReward {},
+
+    // receipt holder unstakes nft by sending its receipt to campaign
+    ReceiveNft(Cw721ReceiveMsg),
+
+    // owner or factory deposits reward by sending reward token with Cw20HookMsg
+    Receive(Cw20ReceiveMsg),
+
+    // owner sweeps reward token sent to campaign without AddRewardToken
+    Reconcile {},
+
+    // soft staking: release staked nfts not owned by their staker anymore, anyone can call
+    Checkpoint {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // factory cancels campaign before it starts, reward can be withdrawn by owner
+    Cancel {},
+
+    // owner registers contracts to be notified on stake, unstake and claim
+    AddHook {
+        addr: String,
+    },
+    RemoveHook {
+        addr: String,
+    },
 }
 
 #[cw_serde]
@@ -40,21 +102,124 @@ pub enum QueryMsg {
     #[returns(CampaignInfo)]
     CampaignInfo {},
 
+    // at_time: pending reward at a future time given current stakes, default current time
     #[returns(NftInfo)]
-    NftInfo { token_id: String },
+    NftInfo {
+        token_id: String,
+        at_time: Option<u64>,
+    },
 
     #[returns(NftInfo)]
     Nft { token_id: String },
 
     #[returns(StakedInfoResult)]
-    NftStaked { owner: Addr },
+    NftStaked {
+        owner: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        at_time: Option<u64>,
+    },
 
     #[returns(Vec<NftInfo>)]
-    Nfts { limit: Option<u32> },
+    Nfts {
+        lockup_term: Option<u64>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        at_time: Option<u64>,
+    },
+
+    #[returns(Vec<StakerInfoResult>)]
+    Stakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     #[returns(Uint128)]
-    TotalPendingReward {},
+    TotalPendingReward { at_time: Option<u64> },
 
     #[returns(Vec<String>)]
-    TokenIds {},
+    TokenIds {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(EmissionScheduleResult)]
+    EmissionSchedule {},
+
+    #[returns(Vec<UnallocatedRewardResult>)]
+    UnallocatedReward {},
+
+    // voting power of staked nfts at height, default current height
+    #[returns(VotingPowerAtHeightResponse)]
+    VotingPowerAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+
+    #[returns(TotalPowerAtHeightResponse)]
+    TotalPowerAtHeight { height: Option<u64> },
+
+    #[returns(HooksResponse)]
+    Hooks {},
+
+    #[returns(SolvencyResult)]
+    Solvency {},
+
+    // projected reward of staking nfts now, until at_time or end of their lockup term,
+    // nfts share weight with nfts already staked by staker
+    #[returns(SimulateStakeResult)]
+    SimulateStake {
+        nfts: Vec<NftStake>,
+        staker: Option<String>,
+        at_time: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    AddRewardToken {},
+}
+
+// payload sent to every registered hook
+#[cw_serde]
+pub enum StakeChangedHookMsg {
+    Stake {
+        collection: Addr,
+        token_id: String,
+        owner: Addr,
+        lockup_term: u64,
+        amount: Uint128, // voting power of staked nft
+    },
+    Unstake {
+        collection: Addr,
+        token_id: String,
+        owner: Addr,
+        lockup_term: u64,
+        amount: Uint128, // voting power of unstaked nft
+        reward: Uint128, // reward of unstaked nft moved to owner
+    },
+    // reward is claimed by staker for all its nfts, so there is no token_id or lockup_term
+    Claim {
+        collection: Addr,
+        owner: Addr,
+        amount: Uint128,
+    },
+}
+
+impl StakeChangedHookMsg {
+    /// Wraps the payload into a message executing the hook contract
+    pub fn into_cosmos_msg(self, hook: &Addr) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: hook.to_string(),
+            msg: to_binary(&StakeChangedExecuteMsg::StakeChangeHook(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+// execute msg a hook contract must handle
+#[cw_serde]
+pub enum StakeChangedExecuteMsg {
+    StakeChangeHook(StakeChangedHookMsg),
 }
@@ -0,0 +1,672 @@
+use std::cmp::{min, Ordering};
+use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::ops::Bound;
+
+use cosmwasm_std::{Addr, Decimal256, Empty, Order, Record, Storage, Uint128};
+use cw_storage_plus::PrefixBound;
+
+use crate::error::ContractError;
+use crate::state::{
+    nfts, update_nft, CampaignInfo, NftInfo, NftReward, StakerNftReward, StakerTermReward,
+    TermReward, NFT_REWARDS, REWARD_EVENTS, STAKER_NFT_REWARDS, STAKER_TERM_REWARDS, TERM_REWARDS,
+    UNALLOCATED_REWARD,
+};
+use crate::utils::{
+    add_reward, calc_emission_in_time, decimal_floor, decimal_multiply_ratio, emission_curve,
+    loyalty_multiplier, staker_weight, EmissionCurve,
+};
+
+// The reward of each lockup term is shared between its stakers by weight, then between the
+// nfts of a staker by loyalty multiplier. Terms and (staker, term) groups keep accumulated
+// reward per unit of weight / multiplier, so an update only touches the nfts whose loyalty
+// step or lockup end is reached, and an nft is credited when it is loaded.
+
+/// Accumulates reward of lockup terms from time_calc_nft to current time (at most end_time).
+/// Loyalty steps and lockup ends of nfts in this period are processed in time order.
+pub fn update_pending_reward(
+    storage: &mut dyn Storage,
+    current_time: u64,
+    campaign_info: &mut CampaignInfo,
+) -> Result<(), ContractError> {
+    let until = min(current_time, campaign_info.end_time);
+    let curve = emission_curve(campaign_info)?;
+
+    // unallocated reward of each lockup term in this update
+    let mut unallocated_rewards = BTreeMap::new();
+    loop {
+        let event = REWARD_EVENTS
+            .prefix_range(
+                storage,
+                None,
+                Some(PrefixBound::inclusive(until)),
+                Order::Ascending,
+            )
+            .next()
+            .transpose()?;
+        let Some(((time, token_id), _)) = event else {
+            break;
+        };
+
+        accrue_reward(
+            storage,
+            campaign_info,
+            &curve,
+            &mut unallocated_rewards,
+            time,
+        )?;
+        process_event(storage, campaign_info, time, &token_id)?;
+    }
+    accrue_reward(
+        storage,
+        campaign_info,
+        &curve,
+        &mut unallocated_rewards,
+        until,
+    )?;
+
+    // save reward of lockup terms without nft staked
+    for (term, reward) in unallocated_rewards {
+        let amount = decimal_floor(reward)?;
+        if !amount.is_zero() {
+            UNALLOCATED_REWARD.update(storage, term, |unallocated| {
+                add_reward(unallocated.unwrap_or_default(), amount)
+                    .map_err(ContractError::overflow("add unallocated reward"))
+            })?;
+        }
+        add_reward_dust(campaign_info, reward, amount)?;
+    }
+
+    Ok(())
+}
+
+// credit the reward accrued by an nft to its pending reward, nft is end reward once the
+// campaign is ended
+fn credit_nft(
+    storage: &mut dyn Storage,
+    campaign_info: &mut CampaignInfo,
+    nft: &mut NftInfo,
+) -> Result<(), ContractError> {
+    if let Some(mut position) = NFT_REWARDS.may_load(storage, &nft.token_id)? {
+        let multiplier = position.multiplier;
+        let group_reward =
+            update_staker_reward(storage, campaign_info, nft, Some(&position), multiplier)?;
+        credit_position(storage, nft, &mut position, group_reward)?;
+        NFT_REWARDS.save(storage, &nft.token_id, &position)?;
+    }
+
+    // if campaign is timeout -> nft timeout
+    if campaign_info.time_calc_nft >= campaign_info.end_time {
+        nft.is_end_reward = true;
+    }
+
+    Ok(())
+}
+
+/// Loads nft with the reward accrued since it was last credited, the nft is saved
+pub fn load_nft(
+    storage: &mut dyn Storage,
+    campaign_info: &mut CampaignInfo,
+    token_id: &str,
+) -> Result<NftInfo, ContractError> {
+    let loaded = nfts().load(storage, token_id.to_string())?;
+    let mut nft = loaded.clone();
+    credit_nft(storage, campaign_info, &mut nft)?;
+    update_nft(storage, &loaded, &nft)?;
+
+    Ok(nft)
+}
+
+/// Nft starts accruing reward in its lockup term, with the loyalty multiplier of its
+/// staked time at time_calc_nft
+pub fn start_nft_reward(
+    storage: &mut dyn Storage,
+    campaign_info: &mut CampaignInfo,
+    nft: &NftInfo,
+) -> Result<(), ContractError> {
+    let time = campaign_info.time_calc_nft;
+    let multiplier = loyalty_multiplier(
+        &campaign_info.loyalty_steps,
+        time.saturating_sub(nft.start_time),
+    );
+    let group_reward = update_staker_reward(storage, campaign_info, nft, None, multiplier)?;
+
+    let position = NftReward {
+        multiplier,
+        reward_per_multiplier: group_reward,
+        next_event: next_event_time(campaign_info, nft, time),
+    };
+    REWARD_EVENTS.save(storage, (position.next_event, &nft.token_id), &Empty {})?;
+    NFT_REWARDS.save(storage, &nft.token_id, &position)?;
+
+    Ok(())
+}
+
+/// Nft stops accruing reward, reward accrued until now is credited. Caller saves the nft.
+pub fn stop_nft_reward(
+    storage: &mut dyn Storage,
+    campaign_info: &mut CampaignInfo,
+    nft: &mut NftInfo,
+) -> Result<(), ContractError> {
+    if let Some(mut position) = NFT_REWARDS.may_load(storage, &nft.token_id)? {
+        let group_reward = update_staker_reward(
+            storage,
+            campaign_info,
+            nft,
+            Some(&position),
+            Uint128::zero(),
+        )?;
+        credit_position(storage, nft, &mut position, group_reward)?;
+        REWARD_EVENTS.remove(storage, (position.next_event, &nft.token_id));
+        NFT_REWARDS.remove(storage, &nft.token_id);
+    }
+
+    Ok(())
+}
+
+/// Moves an nft to new owner with its pending reward, an active nft joins the staker group
+/// of new owner. Reward accrued by the nft until now is credited to new owner, including its
+/// rounding remainder. Caller saves the nft.
+pub fn move_nft_reward(
+    storage: &mut dyn Storage,
+    campaign_info: &mut CampaignInfo,
+    nft: &mut NftInfo,
+    new_owner: &Addr,
+) -> Result<(), ContractError> {
+    // pending reward of nft goes with it
+    take_nft_reward(storage, nft)?;
+    let pending_reward = nft.pending_reward;
+
+    let position = NFT_REWARDS.may_load(storage, &nft.token_id)?;
+    if let Some(mut position) = position {
+        // nft leaves the group of previous owner
+        let group_reward = update_staker_reward(
+            storage,
+            campaign_info,
+            nft,
+            Some(&position),
+            Uint128::zero(),
+        )?;
+        nft.owner = new_owner.clone();
+        credit_position(storage, nft, &mut position, group_reward)?;
+
+        // and joins the group of new owner
+        let group_reward =
+            update_staker_reward(storage, campaign_info, nft, None, position.multiplier)?;
+        NFT_REWARDS.save(
+            storage,
+            &nft.token_id,
+            &NftReward {
+                reward_per_multiplier: group_reward,
+                ..position
+            },
+        )?;
+    } else {
+        nft.owner = new_owner.clone();
+    }
+    add_staker_nft_reward(storage, &nft.owner, pending_reward, Decimal256::zero())
+}
+
+/// Pending reward of nft leaves the credited reward of its staker, it is moved to reward
+/// debt of staker or to a new owner
+pub fn take_nft_reward(storage: &mut dyn Storage, nft: &NftInfo) -> Result<(), ContractError> {
+    if nft.pending_reward.is_zero() {
+        return Ok(());
+    }
+    let mut staker_reward = STAKER_NFT_REWARDS
+        .may_load(storage, &nft.owner)?
+        .unwrap_or_default();
+    staker_reward.pending_reward = staker_reward
+        .pending_reward
+        .checked_sub(nft.pending_reward)
+        .map_err(ContractError::overflow("sub nft reward of staker"))?;
+    save_staker_nft_reward(storage, &nft.owner, &staker_reward)
+}
+
+/// Whole units of the rounding remainder of reward credited to nfts of staker, they are
+/// moved to reward debt of staker
+pub fn take_reward_remainder(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+) -> Result<Uint128, ContractError> {
+    let Some(mut staker_reward) = STAKER_NFT_REWARDS.may_load(storage, owner)? else {
+        return Ok(Uint128::zero());
+    };
+    let amount = decimal_floor(staker_reward.reward_remainder)?;
+    staker_reward.reward_remainder = staker_reward
+        .reward_remainder
+        .checked_sub(Decimal256::from_ratio(amount, 1u128))
+        .map_err(ContractError::overflow("sub reward remainder of staker"))?;
+    save_staker_nft_reward(storage, owner, &staker_reward)?;
+
+    Ok(amount)
+}
+
+/// Pending reward of all staked nfts of staker, from the credited reward of its nfts and
+/// the accumulated reward of its groups. Reward must be updated before.
+pub fn staker_pending_reward(
+    storage: &dyn Storage,
+    owner: &Addr,
+) -> Result<Uint128, ContractError> {
+    let staker_nft_reward = STAKER_NFT_REWARDS
+        .may_load(storage, owner)?
+        .unwrap_or_default();
+
+    // rounding remainder of credited reward is rounded together with uncredited reward
+    let mut uncredited_reward = staker_nft_reward.reward_remainder;
+    let staker_rewards =
+        STAKER_TERM_REWARDS
+            .prefix(owner)
+            .range(storage, None, None, Order::Ascending);
+    for item in staker_rewards {
+        let (term, staker_reward) = item?;
+        let term_reward = TERM_REWARDS.load(storage, term)?;
+        let group_reward = accumulate_staker_reward(&term_reward, &staker_reward)?;
+
+        // uncredited = reward per multiplier * total multiplier - reward credited to nfts
+        uncredited_reward =
+            decimal_multiply_ratio(group_reward, staker_reward.total_multiplier, 1u128)?
+                .checked_sub(staker_reward.reward_paid)
+                .and_then(|reward| uncredited_reward.checked_add(reward))
+                .map_err(ContractError::overflow("sum uncredited reward of staker"))?;
+    }
+
+    add_reward(
+        staker_nft_reward.pending_reward,
+        decimal_floor(uncredited_reward)?,
+    )
+    .map_err(ContractError::overflow("sum pending reward of staker"))
+}
+
+// add reward credited to an nft of staker, the rounding remainder stays with the staker
+fn add_staker_nft_reward(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    amount: Uint128,
+    remainder: Decimal256,
+) -> Result<(), ContractError> {
+    if amount.is_zero() && remainder.is_zero() {
+        return Ok(());
+    }
+    let mut staker_reward = STAKER_NFT_REWARDS
+        .may_load(storage, owner)?
+        .unwrap_or_default();
+    staker_reward.pending_reward = add_reward(staker_reward.pending_reward, amount)
+        .map_err(ContractError::overflow("add nft reward of staker"))?;
+    staker_reward.reward_remainder = staker_reward
+        .reward_remainder
+        .checked_add(remainder)
+        .map_err(ContractError::overflow("add reward remainder of staker"))?;
+    save_staker_nft_reward(storage, owner, &staker_reward)
+}
+
+fn save_staker_nft_reward(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    staker_reward: &StakerNftReward,
+) -> Result<(), ContractError> {
+    if staker_reward.pending_reward.is_zero() && staker_reward.reward_remainder.is_zero() {
+        STAKER_NFT_REWARDS.remove(storage, owner);
+    } else {
+        STAKER_NFT_REWARDS.save(storage, owner, staker_reward)?;
+    }
+
+    Ok(())
+}
+
+// accrue reward of lockup terms from time_calc_nft to `time`. Reward of a term without nft
+// is unallocated, with rollover it goes to the other terms unless all terms are empty.
+fn accrue_reward(
+    storage: &mut dyn Storage,
+    campaign_info: &mut CampaignInfo,
+    curve: &EmissionCurve,
+    unallocated_rewards: &mut BTreeMap<u64, Decimal256>,
+    time: u64,
+) -> Result<(), ContractError> {
+    let start_time = campaign_info.time_calc_nft;
+    if time <= start_time {
+        return Ok(());
+    }
+    campaign_info.time_calc_nft = time;
+
+    let emission = calc_emission_in_time(curve, start_time, time)?;
+    if emission.is_zero() {
+        return Ok(());
+    }
+
+    // sum percent of lockup terms having nft staked
+    let mut term_rewards = Vec::with_capacity(campaign_info.lockup_term.len());
+    let mut occupied_percent = Uint128::zero();
+    for term in campaign_info.lockup_term.iter() {
+        let term_reward = TERM_REWARDS
+            .may_load(storage, term.value)?
+            .unwrap_or_default();
+        if term_reward.nft_count > 0 {
+            occupied_percent = occupied_percent
+                .checked_add(term.percent)
+                .map_err(ContractError::overflow("sum percent of lockup terms"))?;
+        }
+        term_rewards.push(term_reward);
+    }
+    let is_rollover = campaign_info.rollover_unallocated && !occupied_percent.is_zero();
+    let total_percent = if is_rollover {
+        occupied_percent
+    } else {
+        Uint128::from(100u128)
+    };
+
+    for (term, mut term_reward) in campaign_info.lockup_term.iter().zip(term_rewards) {
+        let reward = decimal_multiply_ratio(emission, term.percent, total_percent)?;
+        if term_reward.nft_count > 0 {
+            term_reward.reward_per_weight = term_reward
+                .reward_per_weight
+                .checked_add(decimal_multiply_ratio(
+                    reward,
+                    1u128,
+                    term_reward.total_weight,
+                )?)
+                .map_err(ContractError::overflow("accumulate reward per weight"))?;
+            TERM_REWARDS.save(storage, term.value, &term_reward)?;
+        } else if !is_rollover {
+            let unallocated = unallocated_rewards.entry(term.value).or_default();
+            *unallocated = unallocated
+                .checked_add(reward)
+                .map_err(ContractError::overflow("sum unallocated reward"))?;
+        }
+    }
+
+    Ok(())
+}
+
+// nft reaches a loyalty step or the end of its lockup term at `time`
+fn process_event(
+    storage: &mut dyn Storage,
+    campaign_info: &mut CampaignInfo,
+    time: u64,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    let loaded = nfts().load(storage, token_id.to_string())?;
+    let mut nft = loaded.clone();
+
+    if time >= nft.end_time {
+        // nft stake timeout
+        stop_nft_reward(storage, campaign_info, &mut nft)?;
+        nft.is_end_reward = true;
+    } else {
+        // nft joins its staker group with the multiplier of new loyalty step
+        let mut position = NFT_REWARDS.load(storage, token_id)?;
+        let multiplier = loyalty_multiplier(&campaign_info.loyalty_steps, time - nft.start_time);
+        let group_reward =
+            update_staker_reward(storage, campaign_info, &nft, Some(&position), multiplier)?;
+        credit_position(storage, &mut nft, &mut position, group_reward)?;
+
+        REWARD_EVENTS.remove(storage, (position.next_event, token_id));
+        position.multiplier = multiplier;
+        position.next_event = next_event_time(campaign_info, &nft, time);
+        REWARD_EVENTS.save(storage, (position.next_event, token_id), &Empty {})?;
+        NFT_REWARDS.save(storage, token_id, &position)?;
+    }
+
+    update_nft(storage, &loaded, &nft)?;
+
+    Ok(())
+}
+
+// reward per multiplier of staker group updated to reward per weight of its term,
+// += reward per weight of term since last update * staker weight / total multiplier
+fn accumulate_staker_reward(
+    term_reward: &TermReward,
+    staker_reward: &StakerTermReward,
+) -> Result<Decimal256, ContractError> {
+    if staker_reward.total_multiplier.is_zero() {
+        return Ok(staker_reward.reward_per_multiplier);
+    }
+    let reward = term_reward
+        .reward_per_weight
+        .checked_sub(staker_reward.reward_per_weight)
+        .map_err(ContractError::overflow("calc reward of staker"))?;
+    staker_reward
+        .reward_per_multiplier
+        .checked_add(decimal_multiply_ratio(
+            reward,
+            staker_reward.weight,
+            staker_reward.total_multiplier,
+        )?)
+        .map_err(ContractError::overflow("accumulate reward per multiplier"))
+}
+
+// update accumulated reward of the staker group of nft to its lockup term, then the nft
+// at `position` leaves the group and joins it again with `multiplier`. Nft not in group has
+// no position, nft leaving the group has zero multiplier. Weight of staker and total weight
+// of term follow the total multiplier of group. Reward of nft must be credited at the
+// returned reward per multiplier of group.
+fn update_staker_reward(
+    storage: &mut dyn Storage,
+    campaign_info: &CampaignInfo,
+    nft: &NftInfo,
+    position: Option<&NftReward>,
+    multiplier: Uint128,
+) -> Result<Decimal256, ContractError> {
+    let term = nft.lockup_term.value;
+    let key = (&nft.owner, term);
+    let mut term_reward = TERM_REWARDS.may_load(storage, term)?.unwrap_or_default();
+    let mut staker_reward = STAKER_TERM_REWARDS
+        .may_load(storage, key)?
+        .unwrap_or_default();
+
+    let group_reward = accumulate_staker_reward(&term_reward, &staker_reward)?;
+    staker_reward.reward_per_multiplier = group_reward;
+    staker_reward.reward_per_weight = term_reward.reward_per_weight;
+
+    // reward paid to nft is its credited reward per multiplier * multiplier
+    let (prev_multiplier, prev_paid) = match position {
+        Some(position) => (
+            position.multiplier,
+            decimal_multiply_ratio(position.reward_per_multiplier, position.multiplier, 1u128)?,
+        ),
+        None => (Uint128::zero(), Decimal256::zero()),
+    };
+    let paid = decimal_multiply_ratio(group_reward, multiplier, 1u128)?;
+    staker_reward.reward_paid = staker_reward
+        .reward_paid
+        .checked_sub(prev_paid)
+        .and_then(|reward_paid| reward_paid.checked_add(paid))
+        .map_err(ContractError::overflow("update reward paid of staker"))?;
+
+    if prev_multiplier != multiplier {
+        staker_reward.total_multiplier = staker_reward
+            .total_multiplier
+            .checked_sub(prev_multiplier)
+            .and_then(|total_multiplier| total_multiplier.checked_add(multiplier))
+            .map_err(ContractError::overflow("update multiplier of staker"))?;
+        let weight = staker_weight(
+            &campaign_info.distribution_mode,
+            staker_reward.total_multiplier,
+        )?;
+        term_reward.total_weight = term_reward
+            .total_weight
+            .checked_sub(staker_reward.weight)
+            .and_then(|total_weight| total_weight.checked_add(weight))
+            .map_err(ContractError::overflow("update total weight of term"))?;
+        staker_reward.weight = weight;
+
+        if prev_multiplier.is_zero() {
+            term_reward.nft_count += 1;
+        } else if multiplier.is_zero() {
+            term_reward.nft_count = term_reward.nft_count.saturating_sub(1);
+        }
+        TERM_REWARDS.save(storage, term, &term_reward)?;
+    }
+
+    if staker_reward.total_multiplier.is_zero() {
+        STAKER_TERM_REWARDS.remove(storage, key);
+    } else {
+        STAKER_TERM_REWARDS.save(storage, key, &staker_reward)?;
+    }
+
+    Ok(group_reward)
+}
+
+// credit reward of nft = (group reward per multiplier - credited) * multiplier, rounded
+// down, the rounding remainder is kept by the staker
+fn credit_position(
+    storage: &mut dyn Storage,
+    nft: &mut NftInfo,
+    position: &mut NftReward,
+    group_reward: Decimal256,
+) -> Result<(), ContractError> {
+    let reward = decimal_multiply_ratio(
+        group_reward
+            .checked_sub(position.reward_per_multiplier)
+            .map_err(ContractError::overflow("calc reward of nft"))?,
+        position.multiplier,
+        1u128,
+    )?;
+    let amount = decimal_floor(reward)?;
+    nft.pending_reward = add_reward(nft.pending_reward, amount)
+        .map_err(ContractError::overflow("add pending reward"))?;
+    let remainder = reward
+        .checked_sub(Decimal256::from_ratio(amount, 1u128))
+        .map_err(ContractError::overflow("calc reward remainder of nft"))?;
+    add_staker_nft_reward(storage, &nft.owner, amount, remainder)?;
+    position.reward_per_multiplier = group_reward;
+
+    Ok(())
+}
+
+// remainder of unallocated reward rounded down to `amount` is dust, whole units are moved
+// to reward_dust
+fn add_reward_dust(
+    campaign_info: &mut CampaignInfo,
+    reward: Decimal256,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let remainder = reward
+        .checked_sub(Decimal256::from_ratio(amount, 1u128))
+        .and_then(|dust| campaign_info.reward_dust_remainder.checked_add(dust))
+        .map_err(ContractError::overflow("add reward dust"))?;
+    let dust = decimal_floor(remainder)?;
+    campaign_info.reward_dust = add_reward(campaign_info.reward_dust, dust)
+        .map_err(ContractError::overflow("add reward dust"))?;
+    campaign_info.reward_dust_remainder = remainder
+        .checked_sub(Decimal256::from_ratio(dust, 1u128))
+        .map_err(ContractError::overflow("add reward dust"))?;
+
+    Ok(())
+}
+
+// next loyalty step of nft after `time`, or end of its lockup term
+fn next_event_time(campaign_info: &CampaignInfo, nft: &NftInfo, time: u64) -> u64 {
+    campaign_info
+        .loyalty_steps
+        .iter()
+        .map(|step| nft.start_time.saturating_add(step.staked_time))
+        .find(|&step_time| step_time > time && step_time < nft.end_time)
+        .unwrap_or(nft.end_time)
+}
+
+/// Storage writing on top of a read-only storage, queries update reward in it without saving
+#[derive(Clone)]
+pub struct OverlayStorage<'a> {
+    base: &'a dyn Storage,
+    changes: BTreeMap<Vec<u8>, Option<Vec<u8>>>, // None = removed
+}
+
+impl<'a> OverlayStorage<'a> {
+    pub fn new(base: &'a dyn Storage) -> Self {
+        OverlayStorage {
+            base,
+            changes: BTreeMap::new(),
+        }
+    }
+}
+
+impl Storage for OverlayStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.changes.get(key) {
+            Some(value) => value.clone(),
+            None => self.base.get(key),
+        }
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        if let (Some(start), Some(end)) = (start, end) {
+            if start >= end {
+                return Box::new(std::iter::empty());
+            }
+        }
+        let bounds = (
+            start.map_or(Bound::Unbounded, |start| Bound::Included(start.to_vec())),
+            end.map_or(Bound::Unbounded, |end| Bound::Excluded(end.to_vec())),
+        );
+        let changes = self.changes.range(bounds);
+        let changes: Box<dyn Iterator<Item = Change>> = match order {
+            Order::Ascending => Box::new(changes),
+            Order::Descending => Box::new(changes.rev()),
+        };
+
+        Box::new(OverlayIter {
+            base: self.base.range(start, end, order).peekable(),
+            changes: changes.peekable(),
+            order,
+        })
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.changes.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.changes.insert(key.to_vec(), None);
+    }
+}
+
+// changed record of overlay, None value when removed
+type Change<'a> = (&'a Vec<u8>, &'a Option<Vec<u8>>);
+
+// records of base and changes merged in order, a change replaces the record of same key
+struct OverlayIter<'a, B, C>
+where
+    B: Iterator<Item = Record>,
+    C: Iterator<Item = Change<'a>>,
+{
+    base: Peekable<B>,
+    changes: Peekable<C>,
+    order: Order,
+}
+
+impl<'a, B, C> Iterator for OverlayIter<'a, B, C>
+where
+    B: Iterator<Item = Record>,
+    C: Iterator<Item = Change<'a>>,
+{
+    type Item = Record;
+
+    fn next(&mut self) -> Option<Record> {
+        loop {
+            let ordering = match (self.base.peek(), self.changes.peek()) {
+                (None, None) => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((base_key, _)), Some((change_key, _))) => match self.order {
+                    Order::Ascending => base_key.as_slice().cmp(change_key.as_slice()),
+                    Order::Descending => change_key.as_slice().cmp(base_key.as_slice()),
+                },
+            };
+            if ordering == Ordering::Less {
+                return self.base.next();
+            }
+            if ordering == Ordering::Equal {
+                self.base.next();
+            }
+            if let Some((key, Some(value))) = self.changes.next() {
+                return Some((key.clone(), value.clone()));
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use std::fmt;
 
 use cosmwasm_schema::cw_serde; // attribute macro to (de)serialize and make schemas
-use cosmwasm_std::{Addr, Uint128}; // address type
-use cw_storage_plus::{Item, Map}; // analog of Singletons for storage
+use cosmwasm_std::{Addr, Decimal256, Empty, StdResult, Storage, Uint128}; // address type
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotItem, SnapshotMap, Strategy,
+}; // analog of Singletons for storage
 
 #[cw_serde]
 pub enum TokenInfo {
@@ -60,6 +62,58 @@ impl fmt::Display for LockupTerm {
     }
 }
 
+// how the reward of a lockup term is shared between stakers
+#[cw_serde]
+#[derive(Default)]
+pub enum DistributionMode {
+    // every nft receives the same share
+    #[default]
+    Linear,
+    // share of a staker scales with sqrt(nfts staked)
+    SquareRoot,
+}
+
+// how reward is paid to stakers
+#[cw_serde]
+#[derive(Default)]
+pub enum RewardMode {
+    // reward is transferred from the balance added by AddRewardToken
+    #[default]
+    Transfer,
+    // reward is minted on claim, campaign must be a minter of reward token
+    Mint {
+        reward_per_second: Uint128,
+        mint_cap: Option<Uint128>, // max total reward emitted by campaign, default no cap
+    },
+}
+
+// multiplier (percent, 100 = x1) of nft weight after staked_time seconds of continuous staking
+#[cw_serde]
+pub struct LoyaltyStep {
+    pub staked_time: u64,
+    pub multiplier: Uint128,
+}
+
+impl fmt::Display for LoyaltyStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.staked_time, self.multiplier)
+    }
+}
+
+// emission phase lasts from start_time to start_time of next phase or end_time of campaign,
+// reward per second of phase is proportional to weight
+#[cw_serde]
+pub struct EmissionPhase {
+    pub start_time: u64,
+    pub weight: Uint128,
+}
+
+impl fmt::Display for EmissionPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.start_time, self.weight)
+    }
+}
+
 #[cw_serde]
 pub struct CampaignInfo {
     pub owner: Addr, // owner of campaign
@@ -77,6 +131,18 @@ pub struct CampaignInfo {
     pub time_calc_nft: u64,
     pub start_time: u64, // start time must be from T + 1
     pub end_time: u64,   // max 3 years
+    pub distribution_mode: DistributionMode,
+    pub loyalty_steps: Vec<LoyaltyStep>, // empty = no loyalty multiplier
+    pub emission_schedule: Vec<EmissionPhase>, // empty = constant reward_per_second
+    pub rollover_unallocated: bool,      // share of lockup term without nft goes to other terms
+    pub reward_dust: Uint128,            // rounding remainder of unallocated reward
+    pub reward_dust_remainder: Decimal256, // fraction of rounding remainder below 1
+    pub receipt_collection: Option<Addr>, // receipt of staked nft, position follows receipt holder
+    pub reward_mode: RewardMode,
+    pub soft_staking: bool, // staked nft stays in owner wallet, ownership is checked
+    pub voting_power_by_lockup: bool, // voting power of nft = days of lockup term, default 1
+    pub protocol_fee_bps: u64, // fee on claimed reward, 100 = 1%
+    pub protocol_fee_recipient: Option<Addr>,
 }
 
 pub enum UpdateCampaign {
@@ -93,9 +159,10 @@ pub enum UpdateCampaign {
 
 #[cw_serde]
 pub struct StakerRewardAssetInfo {
-    pub token_ids: Vec<String>,
+    pub nft_count: u64,       // nfts of staker are listed by owner index of nfts
     pub reward_debt: Uint128, // can claim reward.
     pub reward_claimed: Uint128,
+    pub protocol_fee_paid: Uint128,
 }
 
 #[cw_serde]
@@ -105,7 +172,7 @@ pub struct NftInfo {
     pub pending_reward: Uint128,
     pub lockup_term: LockupTerm, // value = seconds
     pub is_end_reward: bool,
-    pub start_time: u64,
+    pub start_time: u64, // kept on renew, loyalty is counted from this time
     pub end_time: u64,
 }
 
@@ -118,16 +185,140 @@ pub struct NftStake {
 // campaign info
 pub const CAMPAIGN_INFO: Item<CampaignInfo> = Item::new("campaign_info");
 
+// contract instantiating the campaign, allowed to deposit reward like the owner
+pub const FACTORY: Item<Addr> = Item::new("factory");
+
 // Mapping from staker address to staked nft.
 pub const STAKERS_INFO: Map<Addr, StakerRewardAssetInfo> = Map::new("stakers_info");
 
-pub const STAKERS: Map<u64, Addr> = Map::new("staker");
+// number of stakers having nft staked
+pub const CURRENT_STAKERS: Item<u64> = Item::new("current_stakers");
+
+// number of stakers having ever staked nft
+pub const TOTAL_STAKERS: Item<u64> = Item::new("total_stakers");
 
-// list token_id nft
-pub const TOKEN_IDS: Item<Vec<String>> = Item::new("token_ids");
+// total nft staked
+pub const TOTAL_NFT_STAKED: Item<u64> = Item::new("total_nft_staked");
+
+// indexes of nft staked by owner, lockup term and end time
+pub struct NftIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, NftInfo, String>,
+    pub lockup_term: MultiIndex<'a, u64, NftInfo, String>,
+    pub end_time: MultiIndex<'a, u64, NftInfo, String>,
+}
+
+impl<'a> IndexList<NftInfo> for NftIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<NftInfo>> + '_> {
+        let v: Vec<&dyn Index<NftInfo>> = vec![&self.owner, &self.lockup_term, &self.end_time];
+        Box::new(v.into_iter())
+    }
+}
 
 // list nft staked
-pub const NFTS: Map<String, NftInfo> = Map::new("nfts");
+pub fn nfts<'a>() -> IndexedMap<'a, String, NftInfo, NftIndexes<'a>> {
+    let indexes = NftIndexes {
+        owner: MultiIndex::new(|_pk, nft| nft.owner.clone(), "nfts", "nfts__owner"),
+        lockup_term: MultiIndex::new(
+            |_pk, nft| nft.lockup_term.value,
+            "nfts",
+            "nfts__lockup_term",
+        ),
+        end_time: MultiIndex::new(|_pk, nft| nft.end_time, "nfts", "nfts__end_time"),
+    };
+    IndexedMap::new("nfts", indexes)
+}
+
+// primary storage of nfts(), index entries only keep the token_id
+const NFTS_PRIMARY: Map<String, NftInfo> = Map::new("nfts");
+
+/// Saves an updated nft, skips unchanged nft and only rewrites indexes when an
+/// indexed value (owner, lockup term or end time) changed
+pub fn update_nft(storage: &mut dyn Storage, old: &NftInfo, new: &NftInfo) -> StdResult<()> {
+    if old == new {
+        return Ok(());
+    }
+    if old.owner == new.owner
+        && old.lockup_term.value == new.lockup_term.value
+        && old.end_time == new.end_time
+    {
+        return NFTS_PRIMARY.save(storage, new.token_id.clone(), new);
+    }
+    nfts().replace(storage, new.token_id.clone(), Some(new), Some(old))
+}
+
+// Snapshot of voting power of staked nfts by staker
+pub const STAKED_POWER: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "staked_power",
+    "staked_power__checkpoints",
+    "staked_power__changelog",
+    Strategy::EveryBlock,
+);
+
+// Snapshot of total voting power of staked nfts
+pub const TOTAL_POWER: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_power",
+    "total_power__checkpoints",
+    "total_power__changelog",
+    Strategy::EveryBlock,
+);
+
+// Contracts notified on stake, unstake and claim
+pub const HOOKS: Item<Vec<Addr>> = Item::new("hooks");
+
+// Mapping from lockup term value to reward not allocated to any nft
+pub const UNALLOCATED_REWARD: Map<u64, Uint128> = Map::new("unallocated_reward");
+
+// accumulated reward of a lockup term, shared between its stakers by weight
+#[cw_serde]
+#[derive(Default)]
+pub struct TermReward {
+    pub nft_count: u64,                // active nfts, term without nft gets no reward
+    pub total_weight: Uint128,         // sum of weight of stakers in term
+    pub reward_per_weight: Decimal256, // accumulated reward per unit of staker weight
+}
+
+// accumulated reward of the active nfts of a staker in a lockup term, shared by multiplier
+#[cw_serde]
+#[derive(Default)]
+pub struct StakerTermReward {
+    pub total_multiplier: Uint128,     // sum of multiplier of active nfts
+    pub weight: Uint128,               // weight of staker in term, by distribution mode
+    pub reward_per_weight: Decimal256, // reward_per_weight of term when last updated
+    pub reward_per_multiplier: Decimal256, // accumulated reward per unit of multiplier
+    pub reward_paid: Decimal256, // sum of reward_per_multiplier credited to each nft * multiplier
+}
+
+// reward position of an active nft in its staker group
+#[cw_serde]
+pub struct NftReward {
+    pub multiplier: Uint128,               // loyalty multiplier, 100 = x1
+    pub reward_per_multiplier: Decimal256, // of staker group when reward was last credited
+    pub next_event: u64,                   // time of next loyalty step or end of lockup
+}
+
+// Mapping from lockup term value to its accumulated reward
+pub const TERM_REWARDS: Map<u64, TermReward> = Map::new("term_rewards");
+
+// Mapping from (staker, lockup term value) to accumulated reward of staker in term
+pub const STAKER_TERM_REWARDS: Map<(&Addr, u64), StakerTermReward> =
+    Map::new("staker_term_rewards");
+
+// reward credited to the staked nfts of a staker
+#[cw_serde]
+#[derive(Default)]
+pub struct StakerNftReward {
+    pub pending_reward: Uint128,      // sum of pending reward of staked nfts
+    pub reward_remainder: Decimal256, // rounding remainder of reward credited to nfts
+}
+
+// Mapping from staker to reward credited to its staked nfts
+pub const STAKER_NFT_REWARDS: Map<&Addr, StakerNftReward> = Map::new("staker_nft_rewards");
+
+// Mapping from token_id to reward position of active nft
+pub const NFT_REWARDS: Map<&str, NftReward> = Map::new("nft_rewards");
+
+// active nfts by time of their next reward event, (time, token_id) -> empty
+pub const REWARD_EVENTS: Map<(u64, &str), Empty> = Map::new("reward_events");
 
 // result query
 #[cw_serde]
@@ -137,6 +328,8 @@ pub struct CampaignInfoResult {
     pub campaign_image: String,
     pub campaign_description: String,
     pub total_nft_staked: u64,
+    pub current_stakers: u64,
+    pub total_stakers: u64,
     pub total_reward_claimed: Uint128,
     pub total_reward: Uint128,
     pub limit_per_staker: u64,
@@ -147,11 +340,95 @@ pub struct CampaignInfoResult {
     pub time_calc_nft: u64,
     pub start_time: u64,
     pub end_time: u64,
+    pub distribution_mode: DistributionMode,
+    pub loyalty_steps: Vec<LoyaltyStep>,
+    pub emission_schedule: Vec<EmissionPhase>,
+    pub rollover_unallocated: bool,
+    pub reward_dust: Uint128,
+    pub receipt_collection: Option<Addr>,
+    pub reward_mode: RewardMode,
+    pub soft_staking: bool,
+    pub voting_power_by_lockup: bool,
+    pub protocol_fee_bps: u64,
+    pub protocol_fee_recipient: Option<Addr>,
 }
 
 #[cw_serde]
 pub struct StakedInfoResult {
     pub nfts: Vec<NftInfo>,
-    pub reward_debt: Uint128, // can claim reward.
+    pub reward_debt: Uint128,    // can claim reward.
+    pub reward_claimed: Uint128, // gross reward claimed, protocol fee included
+    pub protocol_fee_paid: Uint128,
+    pub net_reward_claimed: Uint128,
+}
+
+#[cw_serde]
+pub struct StakerInfoResult {
+    pub owner: Addr,
+    pub nft_count: u64,
+    pub reward_debt: Uint128, // can claim reward, includes pending reward of nfts
     pub reward_claimed: Uint128,
 }
+
+#[cw_serde]
+pub struct SimulatedNftResult {
+    pub token_id: String,
+    pub lockup_term: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub projected_reward: Uint128, // reward from start_time to at_time of simulation
+    pub annual_reward: Uint128,    // reward until end of lockup term scaled to a year
+}
+
+#[cw_serde]
+pub struct SimulateStakeResult {
+    pub at_time: u64,
+    pub nfts: Vec<SimulatedNftResult>,
+    pub total_projected_reward: Uint128,
+}
+
+#[cw_serde]
+pub struct SolvencyResult {
+    pub total_liabilities: Uint128, // pending reward of nfts + reward debt of stakers
+    pub recorded_balance: Uint128,  // reward_token.amount of campaign
+    pub actual_balance: Uint128,    // balance of reward token held by campaign
+    pub surplus: Uint128,           // actual balance above recorded balance
+    pub deficit: Uint128,           // recorded balance above actual balance
+    pub is_solvent: bool,           // both balances cover total liabilities
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[cw_serde]
+pub struct VotingPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct TotalPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct EmissionPhaseResult {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub reward_per_second: Uint128,
+}
+
+#[cw_serde]
+pub struct EmissionScheduleResult {
+    pub current_reward_per_second: Uint128,
+    pub phases: Vec<EmissionPhaseResult>, // current and upcoming phases
+}
+
+#[cw_serde]
+pub struct UnallocatedRewardResult {
+    pub lockup_term: u64,
+    pub amount: Uint128,
+}
@@ -1,4 +1,18 @@
-use cosmwasm_std::{DivideByZeroError, OverflowError, Uint128};
+use cosmwasm_std::{Decimal256, OverflowError, Uint128, Uint256};
+use integer_sqrt::IntegerSquareRoot;
+
+use crate::error::ContractError;
+use crate::state::{
+    CampaignInfo, DistributionMode, EmissionPhase, EmissionPhaseResult, LoyaltyStep, RewardMode,
+};
+
+// scale of staker weight in SquareRoot mode, keeps 6 decimals of sqrt(total_multiplier)
+const SQRT_WEIGHT_SCALE: u128 = 1_000_000_000_000;
+// multiplier of nft weight without loyalty step, 100 = x1
+pub const BASE_MULTIPLIER: u128 = 100;
+// protocol fee on claimed reward in basis points, 10000 = 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_PROTOCOL_FEE_BPS: u64 = 1_000;
 
 /// Calculates the reward amount
 pub fn add_reward(current_reward: Uint128, calc_reward: Uint128) -> Result<Uint128, OverflowError> {
@@ -9,23 +23,191 @@ pub fn sub_reward(current_reward: Uint128, calc_reward: Uint128) -> Result<Uint1
     current_reward.checked_sub(calc_reward)
 }
 
-pub fn calc_reward_in_time(
+/// Protocol fee taken from a claimed reward, rounding down
+pub fn calc_protocol_fee(amount: Uint128, protocol_fee_bps: u64) -> Uint128 {
+    // fee is never greater than amount, so the ratio can not overflow
+    amount.multiply_ratio(protocol_fee_bps, BPS_DENOMINATOR)
+}
+
+/// Multiplies a fixed-point reward by `numerator / denominator`, rounding down
+/// to 18 decimal places
+pub fn decimal_multiply_ratio(
+    reward: Decimal256,
+    numerator: impl Into<Uint256>,
+    denominator: impl Into<Uint256>,
+) -> Result<Decimal256, ContractError> {
+    let reward = reward
+        .atomics()
+        .checked_mul(numerator.into())
+        .map_err(ContractError::overflow("multiply reward ratio"))?
+        .checked_div(denominator.into())
+        .map_err(ContractError::divide_by_zero("multiply reward ratio"))?;
+
+    Ok(Decimal256::new(reward))
+}
+
+/// Integer part of a fixed-point reward
+pub fn decimal_floor(reward: Decimal256) -> Result<Uint128, ContractError> {
+    reward
+        .to_uint_floor()
+        .try_into()
+        .map_err(ContractError::conversion_overflow("floor reward"))
+}
+
+// emission of campaign, phase emits total_reward * weight / total_weight per second
+pub(crate) struct EmissionCurve {
+    total_reward: Uint128,
+    total_weight: Uint256,            // sum(weight * duration) of phases
+    phases: Vec<(u64, u64, Uint128)>, // (start_time, end_time, weight)
+    mint_cap: Option<Uint128>,        // emission stops once total emission reaches mint cap
+}
+
+// campaign without emission schedule has one phase from start_time to end_time
+pub(crate) fn emission_curve(campaign_info: &CampaignInfo) -> Result<EmissionCurve, ContractError> {
+    let schedule = if campaign_info.emission_schedule.is_empty() {
+        vec![EmissionPhase {
+            start_time: campaign_info.start_time,
+            weight: Uint128::one(),
+        }]
+    } else {
+        campaign_info.emission_schedule.clone()
+    };
+
+    // phase ends at start_time of next phase, last phase ends at end_time of campaign
+    let end_times = schedule
+        .iter()
+        .skip(1)
+        .map(|phase| phase.start_time)
+        .chain([campaign_info.end_time]);
+
+    let mut curve = EmissionCurve {
+        total_reward: campaign_info.total_reward,
+        total_weight: Uint256::zero(),
+        phases: Vec::with_capacity(schedule.len()),
+        mint_cap: match campaign_info.reward_mode {
+            RewardMode::Mint { mint_cap, .. } => mint_cap,
+            RewardMode::Transfer => None,
+        },
+    };
+    for (phase, end_time) in schedule.iter().zip(end_times) {
+        let duration = Uint256::from(end_time.saturating_sub(phase.start_time));
+        curve.total_weight = duration
+            .checked_mul(phase.weight.into())
+            .and_then(|weight| curve.total_weight.checked_add(weight))
+            .map_err(ContractError::overflow("sum emission weight"))?;
+        curve
+            .phases
+            .push((phase.start_time, end_time, phase.weight));
+    }
+
+    Ok(curve)
+}
+
+/// Emission phases of campaign with their reward per second. Campaign without
+/// emission schedule has one phase from start_time to end_time.
+pub fn emission_phases(
+    campaign_info: &CampaignInfo,
+) -> Result<Vec<EmissionPhaseResult>, ContractError> {
+    let curve = emission_curve(campaign_info)?;
+
+    let mut phases = Vec::with_capacity(curve.phases.len());
+    for (start_time, end_time, weight) in curve.phases {
+        // reward per second of phase = total_reward * weight / sum(weight * duration)
+        let reward_per_second = if curve.total_weight.is_zero() {
+            Uint128::zero()
+        } else {
+            curve
+                .total_reward
+                .full_mul(weight)
+                .checked_div(curve.total_weight)
+                .map_err(ContractError::divide_by_zero("calc reward per second"))?
+                .try_into()
+                .map_err(ContractError::conversion_overflow("calc reward per second"))?
+        };
+        phases.push(EmissionPhaseResult {
+            start_time,
+            end_time,
+            reward_per_second,
+        });
+    }
+
+    Ok(phases)
+}
+
+// reward emitted by the campaign from start_time to end_time, at most mint cap in total
+pub(crate) fn calc_emission_in_time(
+    curve: &EmissionCurve,
+    start_time: u64,
+    end_time: u64,
+) -> Result<Decimal256, ContractError> {
+    if curve.total_weight.is_zero() {
+        return Ok(Decimal256::zero());
+    }
+
+    let emission = decimal_multiply_ratio(
+        Decimal256::from_ratio(curve.total_reward, 1u128),
+        emission_weighted_time(curve, start_time, end_time)?,
+        curve.total_weight,
+    )?;
+    let Some(mint_cap) = curve.mint_cap else {
+        return Ok(emission);
+    };
+
+    // emission is limited to the part of mint cap not emitted before start_time
+    let emitted = decimal_multiply_ratio(
+        Decimal256::from_ratio(curve.total_reward, 1u128),
+        emission_weighted_time(curve, 0, start_time)?,
+        curve.total_weight,
+    )?;
+    let remaining = Decimal256::from_ratio(mint_cap, 1u128).saturating_sub(emitted);
+
+    Ok(emission.min(remaining))
+}
+
+// sum(weight * duration) of phases from start_time to end_time
+fn emission_weighted_time(
+    curve: &EmissionCurve,
     start_time: u64,
     end_time: u64,
-    reward_per_second: Uint128,
-    percent: Uint128,
-    nft_count: u128,
-) -> Result<Uint128, DivideByZeroError> {
-    let diff_time = end_time.checked_sub(start_time).unwrap();
-
-    let mul_reward = Uint128::from(diff_time)
-        .checked_mul(reward_per_second)
-        .and_then(|res| res.checked_mul(percent))
-        .unwrap();
-
-    let divisor = Uint128::from(100u128)
-        .checked_mul(Uint128::from(nft_count))
-        .unwrap();
-
-    mul_reward.checked_div(divisor)
+) -> Result<Uint256, ContractError> {
+    let mut weighted_time = Uint256::zero();
+    for &(phase_start_time, phase_end_time, weight) in curve.phases.iter() {
+        let from = start_time.max(phase_start_time);
+        let to = end_time.min(phase_end_time);
+        if to > from {
+            weighted_time = Uint256::from(to - from)
+                .checked_mul(weight.into())
+                .and_then(|weight| weighted_time.checked_add(weight))
+                .map_err(ContractError::overflow("sum emission time"))?;
+        }
+    }
+
+    Ok(weighted_time)
+}
+
+/// Multiplier (percent) of an nft weight after `staked_time` seconds of continuous staking
+pub fn loyalty_multiplier(loyalty_steps: &[LoyaltyStep], staked_time: u64) -> Uint128 {
+    loyalty_steps
+        .iter()
+        .rev()
+        .find(|step| staked_time >= step.staked_time)
+        .map(|step| step.multiplier)
+        .unwrap_or(Uint128::from(BASE_MULTIPLIER))
+}
+
+/// Weight of a staker whose active nfts in a lockup term sum up to `total_multiplier`
+pub fn staker_weight(
+    distribution_mode: &DistributionMode,
+    total_multiplier: Uint128,
+) -> Result<Uint128, ContractError> {
+    match distribution_mode {
+        DistributionMode::Linear => Ok(total_multiplier),
+        DistributionMode::SquareRoot => Ok(Uint128::from(
+            total_multiplier
+                .checked_mul(Uint128::from(SQRT_WEIGHT_SCALE))
+                .map_err(ContractError::overflow("calc staker weight"))?
+                .u128()
+                .integer_sqrt(),
+        )),
+    }
 }